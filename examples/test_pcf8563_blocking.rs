@@ -108,7 +108,7 @@ fn test_pcf8563(i2c: I2c<'_, Blocking>) -> Result<(), RtcError<I2cError>> {
     let seconds = rtc.ll.seconds().read()?;
     info!("Seconds register - VL: {}, value: {}",
           seconds.vl(),
-          (seconds.seconds_ten() * 10 + seconds.seconds_unit()) as u8);
+          seconds.seconds_ten() * 10 + seconds.seconds_unit());
 
     // Modify timer control using low-level API
     rtc.ll.timer_control().modify(|w| {
@@ -78,4 +78,47 @@ where
     Access: device_driver::ReadCapability + device_driver::WriteCapability,
 {
     op.modify_async(f).await
-}
\ No newline at end of file
+}
+/// Wait for a rising edge on `pin`, giving up after `timeout_us`
+///
+/// Returns `Ok(false)` on timeout. The blocking variant polls the pin in
+/// 1 µs steps of `delay`, so the timeout is a lower bound.
+#[allow(dead_code)]
+#[only_sync]
+fn wait_for_rising_edge_internal<P: EdgeInput, D: DelayNs>(
+    pin: &mut P,
+    delay: &mut D,
+    timeout_us: u32,
+) -> Result<bool, P::Error> {
+    let mut seen_low = false;
+    for _ in 0..=timeout_us {
+        if !seen_low {
+            seen_low = pin.is_low()?;
+        } else if pin.is_high()? {
+            return Ok(true);
+        }
+        delay.delay_us(1);
+    }
+    Ok(false)
+}
+
+/// Wait for a rising edge on `pin`, giving up after `timeout_us`
+///
+/// Returns `Ok(false)` on timeout.
+#[allow(dead_code)]
+#[only_async]
+async fn wait_for_rising_edge_internal<P: EdgeInput, D: DelayNs>(
+    pin: &mut P,
+    delay: &mut D,
+    timeout_us: u32,
+) -> Result<bool, P::Error> {
+    let mut edge = core::pin::pin!(pin.wait_for_rising_edge());
+    let mut timeout = core::pin::pin!(delay.delay_us(timeout_us));
+    core::future::poll_fn(|cx| {
+        if let core::task::Poll::Ready(result) = edge.as_mut().poll(cx) {
+            return core::task::Poll::Ready(result.map(|()| true));
+        }
+        timeout.as_mut().poll(cx).map(|()| Ok(false))
+    })
+    .await
+}
@@ -0,0 +1,82 @@
+use crate::DateTime;
+
+/// Environment variable holding the build time as a Unix timestamp
+pub const BUILD_TIME_ENV: &str = "PCF8563_DD_BUILD_TIME";
+
+/// Compile time of the calling crate as a [`DateTime`](crate::DateTime) (UTC)
+///
+/// Reads the `PCF8563_DD_BUILD_TIME` environment variable (Unix seconds) at
+/// compile time, which the firmware's `build.rs` sets with
+/// [`emit_build_time`](crate::emit_build_time) or an equivalent
+/// `cargo:rustc-env` line. Fails to compile if it is missing, not a number,
+/// or outside 2000-2099.
+#[macro_export]
+macro_rules! build_time {
+    () => {
+        const {
+            match $crate::__parse_build_time(::core::env!(
+                "PCF8563_DD_BUILD_TIME",
+                "set PCF8563_DD_BUILD_TIME from build.rs, e.g. with pcf8563_dd::emit_build_time()"
+            )) {
+                ::core::option::Option::Some(dt) => dt,
+                ::core::option::Option::None => {
+                    ::core::panic!("PCF8563_DD_BUILD_TIME is not a Unix timestamp in 2000-2099")
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+pub const fn __parse_build_time(value: &str) -> Option<DateTime> {
+    let bytes = value.as_bytes();
+    if bytes.is_empty() || bytes.len() > 12 {
+        return None;
+    }
+    let mut seconds: i64 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        seconds = seconds * 10 + (bytes[i] - b'0') as i64;
+        i += 1;
+    }
+    DateTime::from_unix_timestamp(seconds)
+}
+
+/// Emit the build time for [`build_time!()`](crate::build_time) from a build script
+///
+/// Uses `SOURCE_DATE_EPOCH` when set, for reproducible builds, and the
+/// current time otherwise. Add the crate with the `std` feature under
+/// `[build-dependencies]` and call this from `build.rs`.
+///
+/// The time is refreshed whenever `SOURCE_DATE_EPOCH`, `build.rs`,
+/// `Cargo.toml` or anything under `src/` of the calling package changes, so
+/// it is the time of the last build after a source change; rebuilding
+/// unchanged sources keeps the previous time. Emitting these triggers
+/// replaces Cargo's default of rerunning on any package change, so add
+/// `cargo:rerun-if-changed` lines for other inputs (e.g. `memory.x`) to
+/// `build.rs` as needed.
+#[cfg(feature = "std")]
+pub fn emit_build_time() {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs())
+        });
+    std::println!("cargo:rustc-env={BUILD_TIME_ENV}={seconds}");
+    std::println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // A missing path would rerun the script, and rebuild the caller, on every build
+    let package =
+        std::path::PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    for path in ["src", "build.rs", "Cargo.toml"] {
+        if package.join(path).exists() {
+            std::println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Gregorian calendar helpers shared by validation and conversions
+
+/// Returns `true` if `year` (full year, e.g. 2024) is a leap year
+pub(crate) const fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// Number of days in `month` (1-12) of `year`, or 0 for an invalid month
+pub(crate) const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Full year for a two-digit register year and the century flag
+///
+/// Matches the convention used by the `rtcc` implementation: C = 0 selects
+/// 20xx, C = 1 selects 19xx.
+pub(crate) const fn full_year(year: u8, century: bool) -> u16 {
+    let base = if century { 1900 } else { 2000 };
+    base + year as u16
+}
+
+/// Days since 1970-01-01 for a Gregorian date
+pub(crate) const fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+    let y = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Gregorian (year, month, day) for a count of days since 1970-01-01
+pub(crate) const fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Weekday (0 = Sunday) of a Gregorian date
+pub(crate) const fn weekday(year: u16, month: u8, day: u8) -> u8 {
+    // 1970-01-01 was a Thursday
+    (days_from_civil(year, month, day) + 4).rem_euclid(7) as u8
+}
+
+/// Build a validated 2000-2099 [`DateTime`](crate::DateTime) with the weekday derived from the date
+pub(crate) const fn datetime(
+    year: u16,
+    month: u8,
+    day: u8,
+    hours: u8,
+    minutes: u8,
+    seconds: u8,
+) -> Option<crate::DateTime> {
+    if year < 2000
+        || year > 2099
+        || month < 1
+        || month > 12
+        || day < 1
+        || day > days_in_month(year, month)
+        || hours > 23
+        || minutes > 59
+        || seconds > 59
+    {
+        return None;
+    }
+    Some(crate::DateTime {
+        year: (year - 2000) as u8,
+        month,
+        day,
+        weekday: weekday(year, month, day),
+        hours,
+        minutes,
+        seconds,
+    })
+}
@@ -0,0 +1,49 @@
+use crate::RtcError;
+use thiserror::Error;
+
+/// Free-running MCU tick source (e.g. a SysTick or timer counter extended to 64 bits)
+pub trait Monotonic {
+    /// Current tick count
+    fn now(&mut self) -> u64;
+    /// Tick frequency in Hz
+    fn tick_hz(&self) -> u32;
+}
+
+/// Result of an MCU clock calibration against CLKOUT
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct McuCalibration {
+    /// MCU oscillator error in ppm (positive: MCU clock runs fast)
+    pub ppm: f32,
+    /// Number of CLKOUT periods measured
+    pub edges: u32,
+    /// MCU ticks counted over the measurement window
+    pub measured_ticks: u64,
+    /// MCU ticks expected over the same window for a perfect MCU clock
+    pub expected_ticks: u64,
+}
+
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CalibrationError<I2cErr, CaptureErr> {
+    #[error("RTC error")]
+    Rtc(RtcError<I2cErr>),
+    #[error("Edge capture error")]
+    Capture(CaptureErr),
+    #[error("Unsupported calibration frequency")]
+    UnsupportedFrequency,
+    /// The measurement window must span at least one edge
+    #[error("Invalid edge count")]
+    InvalidEdgeCount,
+}
+
+/// Error of [`EdgeCounter`](crate::EdgeCounter)
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CaptureError<PinErr> {
+    #[error("Edge input error")]
+    Pin(PinErr),
+    /// No rising edge within the edge timeout (e.g. CLKOUT not wired)
+    #[error("Timed out waiting for an edge")]
+    Timeout,
+}
@@ -0,0 +1,97 @@
+use super::{DelayNs, EdgeInput, RegisterInterface, bisync, only_async, only_sync};
+use crate::{CaptureError, Monotonic};
+
+include!("bisync_helpers.rs");
+
+/// Source of CLKOUT edge timing used by
+/// [`calibrate_mcu_clock`](super::Pcf8563::calibrate_mcu_clock)
+///
+/// Implement this on top of a timer input-capture peripheral for the best
+/// resolution, or use [`EdgeCounter`] with a GPIO and a [`Monotonic`] tick source.
+#[bisync]
+#[allow(async_fn_in_trait)]
+pub trait ClkoutCapture {
+    type Error: core::fmt::Debug;
+
+    /// Frequency of the MCU ticks returned by [`measure_edges`](Self::measure_edges)
+    fn tick_hz(&self) -> u32;
+
+    /// Wait for a rising edge on CLKOUT, then return the number of MCU ticks
+    /// elapsed until `edges` further rising edges have been seen
+    async fn measure_edges(&mut self, edges: u32) -> Result<u64, Self::Error>;
+}
+
+/// [`ClkoutCapture`] implementation that counts edges on a GPIO input
+/// and timestamps them with a [`Monotonic`] tick source
+///
+/// The blocking variant polls the pin in 1 µs steps, so it is only suitable
+/// for the 1 Hz and low kHz CLKOUT settings. Each edge must arrive within
+/// the edge timeout (default 2 s), otherwise the measurement fails with
+/// [`CaptureError::Timeout`].
+pub struct EdgeCounter<P, M, D> {
+    pin: P,
+    monotonic: M,
+    delay: D,
+    edge_timeout_us: u32,
+}
+
+impl<P, M, D> EdgeCounter<P, M, D> {
+    pub fn new(pin: P, monotonic: M, delay: D) -> Self {
+        Self {
+            pin,
+            monotonic,
+            delay,
+            edge_timeout_us: 2_000_000,
+        }
+    }
+
+    /// Set the maximum wait for each edge in microseconds
+    pub fn set_edge_timeout(&mut self, timeout_us: u32) {
+        self.edge_timeout_us = timeout_us;
+    }
+
+    /// Release the pin, the tick source and the delay
+    pub fn release(self) -> (P, M, D) {
+        (self.pin, self.monotonic, self.delay)
+    }
+}
+
+#[bisync]
+impl<P, M, D> ClkoutCapture for EdgeCounter<P, M, D>
+where
+    P: EdgeInput,
+    M: Monotonic,
+    D: DelayNs,
+{
+    type Error = CaptureError<P::Error>;
+
+    fn tick_hz(&self) -> u32 {
+        self.monotonic.tick_hz()
+    }
+
+    async fn measure_edges(&mut self, edges: u32) -> Result<u64, Self::Error> {
+        self.wait_for_edge().await?;
+        let start = self.monotonic.now();
+        for _ in 0..edges {
+            self.wait_for_edge().await?;
+        }
+        Ok(self.monotonic.now().wrapping_sub(start))
+    }
+}
+
+#[bisync]
+impl<P, M, D> EdgeCounter<P, M, D>
+where
+    P: EdgeInput,
+    D: DelayNs,
+{
+    async fn wait_for_edge(&mut self) -> Result<(), CaptureError<P::Error>> {
+        match wait_for_rising_edge_internal(&mut self.pin, &mut self.delay, self.edge_timeout_us)
+            .await
+        {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(CaptureError::Timeout),
+            Err(e) => Err(CaptureError::Pin(e)),
+        }
+    }
+}
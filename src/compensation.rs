@@ -0,0 +1,200 @@
+use super::{Pcf8563, RegisterInterface, bisync};
+use crate::drift::whole_seconds;
+use crate::{DateTime, DriftParams, DriftSample, RtcError, TemperatureCompensation};
+
+/// Driver wrapper that corrects for crystal drift in software
+///
+/// Every [`get_datetime`](Self::get_datetime) is corrected by the configured
+/// ppm error accumulated since the last sync, and optionally by a crystal
+/// temperature model fed with periodic temperature samples. With a
+/// write-back threshold set, the corrected time is written to the chip once
+/// the accumulated error reaches it, so the chip itself stays close to true
+/// time.
+///
+/// The parameters are plain data ([`DriftParams`]); persist them with
+/// [`params`](Self::params) and restore them with [`new`](Self::new). No
+/// correction or write-back happens before the first trusted
+/// [`set_datetime`](Self::set_datetime).
+pub struct DriftCompensated<I2CImpl, I2CBusErr>
+where
+    I2CImpl: RegisterInterface<AddressType = u8, Error = RtcError<I2CBusErr>>,
+    I2CBusErr: core::fmt::Debug,
+{
+    rtc: Pcf8563<I2CImpl, I2CBusErr>,
+    params: DriftParams,
+    temperature: Option<TemperatureCompensation>,
+    writeback_threshold_ms: Option<u32>,
+}
+
+impl<I2CImpl, I2CBusErr> DriftCompensated<I2CImpl, I2CBusErr>
+where
+    I2CImpl: RegisterInterface<AddressType = u8, Error = RtcError<I2CBusErr>>,
+    I2CBusErr: core::fmt::Debug,
+{
+    /// Wrap a driver with previously stored (or fresh) parameters
+    pub fn new(rtc: Pcf8563<I2CImpl, I2CBusErr>, params: DriftParams) -> Self {
+        Self {
+            rtc,
+            params,
+            temperature: None,
+            writeback_threshold_ms: None,
+        }
+    }
+
+    /// Write corrected time back to the chip once the accumulated error
+    /// reaches `threshold_ms` (`None` disables write-back)
+    ///
+    /// Only whole seconds are written, so thresholds below 500 ms behave
+    /// like 500 ms.
+    pub fn set_writeback_threshold(&mut self, threshold_ms: Option<u32>) {
+        self.writeback_threshold_ms = threshold_ms;
+    }
+
+    /// Current parameters, for persistent storage
+    pub fn params(&self) -> DriftParams {
+        self.params
+    }
+
+    /// Replace the parameters, e.g. with a new ppm estimate
+    pub fn set_params(&mut self, params: DriftParams) {
+        self.params = params;
+    }
+
+    /// Enable temperature compensation on top of the ppm correction
+    ///
+    /// Pass previously stored state to resume, or `None` to disable it.
+    /// Samples are added with
+    /// [`add_temperature_sample`](Self::add_temperature_sample).
+    pub fn set_temperature_compensation(&mut self, temperature: Option<TemperatureCompensation>) {
+        self.temperature = temperature;
+    }
+
+    /// Current temperature compensation state, for persistent storage
+    pub fn temperature_compensation(&self) -> Option<TemperatureCompensation> {
+        self.temperature
+    }
+
+    /// Access the wrapped driver
+    ///
+    /// Setting the time through it bypasses the sync bookkeeping; use
+    /// [`set_datetime`](Self::set_datetime) instead.
+    pub fn rtc(&mut self) -> &mut Pcf8563<I2CImpl, I2CBusErr> {
+        &mut self.rtc
+    }
+
+    /// Destroy the wrapper and return the driver and current parameters
+    pub fn release(self) -> (Pcf8563<I2CImpl, I2CBusErr>, DriftParams) {
+        (self.rtc, self.params)
+    }
+
+    /// Get the drift-corrected date and time
+    ///
+    /// Falls back to the uncorrected reading if the correction would leave
+    /// the 2000-2099 range.
+    #[bisync]
+    pub async fn get_datetime(&mut self) -> Result<DateTime, RtcError<I2CBusErr>> {
+        let raw = self.rtc.get_datetime().await?;
+        let raw_ts = raw.to_unix_timestamp();
+        let error_ms = self.error_ms(raw_ts);
+        let applied = whole_seconds(error_ms);
+        let Some(corrected) = DateTime::from_unix_timestamp(raw_ts - applied) else {
+            warn!("PCF8563: drift correction out of range, using raw time");
+            return Ok(raw);
+        };
+
+        if let Some(threshold) = self.writeback_threshold_ms
+            && applied != 0
+            && error_ms.unsigned_abs() >= threshold as u64
+        {
+            debug!("PCF8563: writing back drift correction of {} ms", error_ms);
+            self.rtc.set_datetime(&corrected).await?;
+            self.params.last_sync = Some(raw_ts - applied);
+            self.params.residual_ms = (error_ms - applied * 1000) as i32;
+            if let Some(temperature) = self.temperature.as_mut() {
+                temperature.reset(raw_ts - applied);
+            }
+        }
+
+        Ok(corrected)
+    }
+
+    /// Set the date and time from a trusted reference and restart drift tracking
+    ///
+    /// Reads the RTC first and returns the crystal error observed since the
+    /// previous sync (including the correction already applied), e.g. for a
+    /// [`DriftEstimator`](crate::DriftEstimator) or fleet telemetry. Returns
+    /// `None` if there was no earlier trusted sync, the RTC held no valid
+    /// time (VL set or invalid registers) or the reference did not advance.
+    #[bisync]
+    pub async fn set_datetime(
+        &mut self,
+        dt: &DateTime,
+    ) -> Result<Option<DriftSample>, RtcError<I2CBusErr>> {
+        let reference = dt.to_unix_timestamp();
+        let raw = if self.params.last_reference.is_none() || !self.rtc.is_clock_valid().await? {
+            None
+        } else {
+            match self.rtc.get_datetime().await {
+                Ok(raw) => Some(raw.to_unix_timestamp()),
+                Err(RtcError::InvalidRegisterValue { .. }) => None,
+                Err(e) => return Err(e),
+            }
+        };
+        let sample = raw
+            .zip(self.params.last_reference)
+            .and_then(|(raw_ts, last)| {
+                let error_secs = raw_ts - whole_seconds(self.error_ms(raw_ts)) - reference;
+                let residual = DriftSample::new(error_secs, u32::try_from(reference - last).ok()?)?;
+                Some(DriftSample {
+                    ppm: self.params.ppm + residual.ppm,
+                    ..residual
+                })
+            });
+        if let Some(sample) = sample {
+            debug!("PCF8563: sync observed {:?}", sample);
+        }
+
+        self.rtc.set_datetime(dt).await?;
+        self.params.last_sync = Some(reference);
+        self.params.last_reference = Some(reference);
+        self.params.residual_ms = 0;
+        if let Some(temperature) = self.temperature.as_mut() {
+            temperature.reset(reference);
+        }
+        Ok(sample)
+    }
+
+    /// Feed a temperature measurement (°C) to the temperature compensation
+    ///
+    /// Timestamps it with the current RTC reading. Does nothing unless
+    /// temperature compensation is enabled; call it periodically (e.g. every
+    /// few minutes) so temperature swings are integrated accurately.
+    #[bisync]
+    pub async fn add_temperature_sample(
+        &mut self,
+        celsius: f32,
+    ) -> Result<(), RtcError<I2CBusErr>> {
+        if self.temperature.is_none() {
+            return Ok(());
+        }
+        let raw_ts = self.rtc.get_datetime().await?.to_unix_timestamp();
+        if let Some(temperature) = self.temperature.as_mut() {
+            temperature.add_sample(raw_ts, celsius);
+        }
+        Ok(())
+    }
+
+    /// Total expected error of an RTC reading (ppm drift plus temperature), in ms
+    ///
+    /// Zero until the first trusted sync.
+    fn error_ms(&self, raw_ts: i64) -> i64 {
+        if self.params.last_sync.is_none() {
+            return 0;
+        }
+        self.params.error_ms(raw_ts)
+            + self
+                .temperature
+                .as_ref()
+                .map_or(0, |temperature| temperature.error_ms(raw_ts))
+    }
+}
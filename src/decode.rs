@@ -0,0 +1,117 @@
+use crate::{Alarm, DateTime, Field, RtcError};
+
+/// Decode the 7 time/date registers (0x02-0x08)
+///
+/// Masks the VL and century flags and unused bits.
+pub(crate) fn decode_datetime(buf: &[u8; 7]) -> DateTime {
+    DateTime {
+        seconds: bcd_to_dec(buf[0] & 0x7F), // mask VL flag
+        minutes: bcd_to_dec(buf[1] & 0x7F),
+        hours: bcd_to_dec(buf[2] & 0x3F),
+        day: bcd_to_dec(buf[3] & 0x3F),
+        weekday: buf[4] & 0x07,
+        month: bcd_to_dec(buf[5] & 0x1F), // mask century flag
+        year: bcd_to_dec(buf[6]),
+    }
+}
+
+/// Decode the 7 time registers, rejecting invalid BCD and out-of-range values
+pub(crate) fn decode_datetime_checked<E>(buf: &[u8; 7]) -> Result<DateTime, RtcError<E>> {
+    match invalid_datetime_field(buf) {
+        Some((field, raw)) => Err(RtcError::InvalidRegisterValue { field, raw }),
+        None => Ok(decode_datetime(buf)),
+    }
+}
+
+/// Decode the seconds register (0x02), rejecting invalid BCD and out-of-range values
+///
+/// Masks the VL flag.
+pub(crate) fn decode_seconds_checked<E>(reg: u8) -> Result<u8, RtcError<E>> {
+    let raw = reg & 0x7F;
+    if !is_valid_bcd(raw, 0..=59) {
+        return Err(RtcError::InvalidRegisterValue {
+            field: Field::Seconds,
+            raw,
+        });
+    }
+    Ok(bcd_to_dec(raw))
+}
+
+/// Decode the 4 alarm registers, rejecting invalid BCD and out-of-range values
+///
+/// Disabled components (AE_x set) are not checked.
+pub(crate) fn decode_alarm_checked<E>(buf: &[u8; 4]) -> Result<Alarm, RtcError<E>> {
+    let fields = [
+        (Field::MinuteAlarm, buf[0], 0x7F, 0..=59),
+        (Field::HourAlarm, buf[1], 0x3F, 0..=23),
+        (Field::DayAlarm, buf[2], 0x3F, 1..=31),
+    ];
+    for (field, reg, mask, range) in fields {
+        if reg & 0x80 == 0 && !is_valid_bcd(reg & mask, range) {
+            return Err(RtcError::InvalidRegisterValue {
+                field,
+                raw: reg & mask,
+            });
+        }
+    }
+    if buf[3] & 0x80 == 0 && buf[3] & 0x07 > 6 {
+        return Err(RtcError::InvalidRegisterValue {
+            field: Field::WeekdayAlarm,
+            raw: buf[3] & 0x07,
+        });
+    }
+    Ok(decode_alarm(buf))
+}
+
+/// Decode the 4 alarm registers (0x09-0x0C)
+///
+/// A set AE_x bit (bit 7) means that alarm component is disabled.
+pub(crate) fn decode_alarm(buf: &[u8; 4]) -> Alarm {
+    let enabled = |reg: u8| reg & 0x80 == 0;
+    Alarm {
+        minute: enabled(buf[0]).then(|| bcd_to_dec(buf[0] & 0x7F)),
+        hour: enabled(buf[1]).then(|| bcd_to_dec(buf[1] & 0x3F)),
+        day: enabled(buf[2]).then(|| bcd_to_dec(buf[2] & 0x3F)),
+        weekday: enabled(buf[3]).then_some(buf[3] & 0x07),
+    }
+}
+
+/// Find the first time register (0x02-0x08) holding invalid BCD or an out-of-range value
+///
+/// Returns the field and its raw register value with the VL and century
+/// flags masked out.
+pub(crate) fn invalid_datetime_field(buf: &[u8; 7]) -> Option<(Field, u8)> {
+    let fields = [
+        (Field::Seconds, buf[0] & 0x7F, 0..=59),
+        (Field::Minutes, buf[1] & 0x7F, 0..=59),
+        (Field::Hours, buf[2] & 0x3F, 0..=23),
+        (Field::Day, buf[3] & 0x3F, 1..=31),
+        (Field::Month, buf[5] & 0x1F, 1..=12),
+        (Field::Year, buf[6], 0..=99),
+    ];
+    if buf[4] & 0x07 > 6 {
+        return Some((Field::Weekday, buf[4] & 0x07));
+    }
+    fields
+        .into_iter()
+        .find(|(_, raw, range)| !is_valid_bcd(*raw, range.clone()))
+        .map(|(field, raw, _)| (field, raw))
+}
+
+/// Returns `true` if both nibbles of `bcd` are decimal digits and the value is in `range`
+#[inline]
+pub(crate) fn is_valid_bcd(bcd: u8, range: core::ops::RangeInclusive<u8>) -> bool {
+    bcd & 0x0F <= 9 && bcd >> 4 <= 9 && range.contains(&bcd_to_dec(bcd))
+}
+
+/// Convert BCD to decimal
+#[inline]
+pub(crate) fn bcd_to_dec(bcd: u8) -> u8 {
+    (bcd & 0x0F) + ((bcd >> 4) * 10)
+}
+
+/// Convert decimal to BCD
+#[inline]
+pub(crate) fn dec_to_bcd(dec: u8) -> u8 {
+    ((dec / 10) << 4) | (dec % 10)
+}
@@ -0,0 +1,252 @@
+/// Persistent drift compensation parameters
+///
+/// The PCF8563 has no offset or aging register, so the correction is applied
+/// in software by `DriftCompensated`. Store these values (e.g. in flash) and
+/// pass them back in after a reset.
+///
+/// Until the first trusted sync (`last_sync` is `None`) no correction is
+/// applied, since the time the crystal error accumulated over is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DriftParams {
+    /// Crystal frequency error in ppm (positive: RTC runs fast)
+    pub ppm: f32,
+    /// RTC reading (Unix timestamp) at the last sync or write-back, or
+    /// `None` if the clock was never synced
+    pub last_sync: Option<i64>,
+    /// Sub-second correction left over from the last write-back, in ms
+    pub residual_ms: i32,
+    /// Reference time (Unix timestamp) of the last trusted sync, or `None`
+    /// if the clock was never synced
+    pub last_reference: Option<i64>,
+}
+
+impl DriftParams {
+    /// Parameters for a clock that has not been synced yet
+    ///
+    /// The correction starts with the first trusted `set_datetime`.
+    pub const fn new(ppm: f32) -> Self {
+        Self {
+            ppm,
+            last_sync: None,
+            residual_ms: 0,
+            last_reference: None,
+        }
+    }
+
+    /// Parameters for a clock set to the correct time at `timestamp`
+    pub const fn synced_at(ppm: f32, timestamp: i64) -> Self {
+        Self {
+            ppm,
+            last_sync: Some(timestamp),
+            residual_ms: 0,
+            last_reference: Some(timestamp),
+        }
+    }
+
+    /// Accumulated error of an RTC reading, in ms (positive: RTC ahead)
+    ///
+    /// Zero if the clock was never synced.
+    pub fn error_ms(&self, raw_timestamp: i64) -> i64 {
+        let Some(last_sync) = self.last_sync else {
+            return 0;
+        };
+        let elapsed = (raw_timestamp - last_sync) as f64;
+        let ppm = self.ppm as f64;
+        self.residual_ms as i64 + round(elapsed * 1000.0 * ppm / (1_000_000.0 + ppm))
+    }
+
+    /// Corrected Unix timestamp for an RTC reading
+    pub fn correct(&self, raw_timestamp: i64) -> i64 {
+        raw_timestamp - whole_seconds(self.error_ms(raw_timestamp))
+    }
+}
+
+/// Crystal error observed between two trusted syncs
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DriftSample {
+    /// Observed frequency error in ppm (positive: RTC runs fast)
+    pub ppm: f32,
+    /// Time between the two syncs, in seconds
+    pub interval_secs: u32,
+}
+
+impl DriftSample {
+    /// Sample from an RTC reading taken just before a sync to `reference`,
+    /// `interval_secs` after the previous sync
+    ///
+    /// `rtc_error_secs` is the RTC reading minus the reference. Returns
+    /// `None` for a zero interval.
+    pub fn new(rtc_error_secs: i64, interval_secs: u32) -> Option<Self> {
+        (interval_secs > 0).then(|| Self {
+            ppm: (rtc_error_secs as f64 * 1_000_000.0 / interval_secs as f64) as f32,
+            interval_secs,
+        })
+    }
+
+    /// Resolution of this sample in ppm, from the 1 s granularity of the RTC
+    pub fn resolution_ppm(&self) -> f32 {
+        1_000_000.0 / self.interval_secs as f32
+    }
+}
+
+/// Crystal error estimate from [`DriftEstimator`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DriftEstimate {
+    /// Estimated frequency error in ppm (positive: RTC runs fast)
+    pub ppm: f32,
+    /// One-sigma uncertainty of `ppm`
+    pub uncertainty_ppm: f32,
+    /// Samples used for the estimate
+    pub inliers: usize,
+    /// Samples rejected as outliers (e.g. a bad reference)
+    pub outliers: usize,
+}
+
+/// Estimates the crystal ppm error from the last `N` trusted syncs
+///
+/// Samples deviating from the median by more than three scaled median
+/// absolute deviations (plus their own 1 s resolution) are rejected; the
+/// rest are averaged weighted by their interval.
+#[derive(Debug, Clone)]
+pub struct DriftEstimator<const N: usize> {
+    samples: [DriftSample; N],
+    len: usize,
+    next: usize,
+    min_interval_secs: u32,
+}
+
+impl<const N: usize> DriftEstimator<N> {
+    /// Create an estimator ignoring syncs closer together than `min_interval_secs`
+    ///
+    /// The RTC's 1 s resolution limits a sample to 1e6 / interval ppm, so
+    /// intervals of a day or more are needed for single-digit ppm.
+    pub const fn new(min_interval_secs: u32) -> Self {
+        Self {
+            samples: [DriftSample {
+                ppm: 0.0,
+                interval_secs: 0,
+            }; N],
+            len: 0,
+            next: 0,
+            min_interval_secs,
+        }
+    }
+
+    /// Add a sample, replacing the oldest once `N` are stored
+    ///
+    /// Returns `false` if the sample's interval is too short.
+    pub fn add(&mut self, sample: DriftSample) -> bool {
+        if N == 0 || sample.interval_secs < self.min_interval_secs.max(1) {
+            return false;
+        }
+        self.samples[self.next] = sample;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+        true
+    }
+
+    /// Stored samples, in no particular order
+    pub fn samples(&self) -> &[DriftSample] {
+        &self.samples[..self.len]
+    }
+
+    /// Remove all samples
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+
+    /// Current estimate, or `None` without samples
+    pub fn estimate(&self) -> Option<DriftEstimate> {
+        let samples = self.samples();
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = [0.0f32; N];
+        let sorted = &mut sorted[..samples.len()];
+        for (dst, sample) in sorted.iter_mut().zip(samples) {
+            *dst = sample.ppm;
+        }
+        let center = median(sorted);
+        for value in sorted.iter_mut() {
+            *value = (*value - center).abs();
+        }
+        let scale = 1.4826 * median(sorted);
+
+        let mut weight = 0.0f64;
+        let mut sum = 0.0f64;
+        let mut inliers = 0;
+        for sample in samples {
+            if (sample.ppm - center).abs() <= 3.0 * scale + sample.resolution_ppm() {
+                weight += sample.interval_secs as f64;
+                sum += sample.ppm as f64 * sample.interval_secs as f64;
+                inliers += 1;
+            }
+        }
+        let ppm = (sum / weight) as f32;
+
+        let mut variance = 0.0f64;
+        for sample in samples {
+            if (sample.ppm - center).abs() <= 3.0 * scale + sample.resolution_ppm() {
+                let d = (sample.ppm - ppm) as f64;
+                variance += d * d * sample.interval_secs as f64;
+            }
+        }
+        variance /= weight;
+        // Spread of the samples plus the combined 1 s quantization
+        let quantization = 1_000_000.0 / weight;
+        let uncertainty = sqrt(variance / inliers as f64 + quantization * quantization);
+
+        Some(DriftEstimate {
+            ppm,
+            uncertainty_ppm: uncertainty as f32,
+            inliers,
+            outliers: samples.len() - inliers,
+        })
+    }
+}
+
+/// Median of `values`, sorting them in place
+fn median(values: &mut [f32]) -> f32 {
+    values.sort_unstable_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Square root by Newton's method (no `libm` in `core`)
+fn sqrt(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut y = if x > 1.0 { x } else { 1.0 };
+    for _ in 0..64 {
+        let next = 0.5 * (y + x / y);
+        if next >= y {
+            break;
+        }
+        y = next;
+    }
+    y
+}
+
+/// Round to the nearest integer, away from zero on ties
+pub(crate) fn round(x: f64) -> i64 {
+    if x >= 0.0 {
+        (x + 0.5) as i64
+    } else {
+        (x - 0.5) as i64
+    }
+}
+
+/// Nearest whole number of seconds for a duration in ms
+pub(crate) fn whole_seconds(ms: i64) -> i64 {
+    (ms + 500).div_euclid(1000)
+}
@@ -1,8 +1,20 @@
-use super::{I2c, RegisterInterface, bisync, only_async, only_sync};
+use super::{
+    ClkoutCapture, DelayNs, EdgeInput, I2c, RegisterInterface, bisync, only_async, only_sync,
+};
+use crate::decode::{
+    dec_to_bcd, decode_alarm, decode_alarm_checked, decode_datetime, decode_datetime_checked,
+    decode_seconds_checked,
+};
+use crate::subsec::SubsecAnchor;
 use crate::{
-    Alarm, ClkoutFrequency, DateTime, PCF8563_I2C_ADDR, Pcf8563Interface, Pcf8563LowLevel,
-    RtcError, Time, TimerFrequency, bcd_to_dec, dec_to_bcd,
+    Alarm, CalibrationError, ClkoutFrequency, ClockHealth, DateTime, EdgeError, Field,
+    McuCalibration, Monotonic, NmeaError, Pcf8563Interface, Pcf8563LowLevel, PorOverride,
+    REGISTER_COUNT, RadioTime, ReadMode, RegisterSnapshot, RetryInterface, RetryPolicy, RtcError,
+    STOP_RELEASE_DELAY_US, SUBSEC_RESYNC_SECS, SntpError, SntpMeasurement, SntpRequest,
+    SubsecDateTime, SyncError, SyncOutcome, TestModeStatus, Time, TimerFrequency, Variant,
+    check_field, dump::log_registers, fmt::Bytes, parse_nmea,
 };
+use embedded_hal::i2c::Operation;
 #[cfg(feature = "rtcc")]
 #[only_sync]
 use rtcc::{
@@ -14,7 +26,7 @@ use rtcc::{
 impl<I2CBus, E> RegisterInterface for Pcf8563Interface<I2CBus>
 where
     I2CBus: I2c<Error = E>,
-    E: core::fmt::Debug,
+    E: embedded_hal::i2c::Error,
 {
     type AddressType = u8;
     type Error = RtcError<E>;
@@ -25,9 +37,11 @@ where
         data: &mut [u8],
     ) -> Result<(), Self::Error> {
         self.i2c_bus
-            .write_read(PCF8563_I2C_ADDR, &[address], data)
+            .write_read(self.address, &[address], data)
             .await
-            .map_err(RtcError::I2c)
+            .map_err(RtcError::from_i2c)?;
+        trace!("PCF8563: read  reg {:#04x}: {}", address, Bytes(data));
+        Ok(())
     }
     async fn write_register(
         &mut self,
@@ -35,16 +49,85 @@ where
         _size_bits: u32,
         data: &[u8],
     ) -> Result<(), Self::Error> {
-        let mut buffer = [0u8; 9]; // Max: address + 8 bytes for datetime
-        if (1 + data.len()) > buffer.len() {
-            return Err(RtcError::InvalidInputData);
+        if address as usize + data.len() > REGISTER_COUNT {
+            return Err(RtcError::RegisterOutOfRange {
+                address,
+                len: data.len(),
+            });
         }
-        buffer[0] = address;
-        buffer[1..1 + data.len()].copy_from_slice(data);
+        trace!("PCF8563: write reg {:#04x}: {}", address, Bytes(data));
+        // Adjacent write operations are sent without a repeated start, so the
+        // register address and the data form a single burst without copying.
         self.i2c_bus
-            .write(PCF8563_I2C_ADDR, &buffer[..1 + data.len()])
+            .transaction(
+                self.address,
+                &mut [Operation::Write(&[address]), Operation::Write(data)],
+            )
             .await
-            .map_err(RtcError::I2c)
+            .map_err(RtcError::from_i2c)
+    }
+}
+
+#[bisync]
+impl<Interface, Delay, E> RegisterInterface for RetryInterface<Interface, Delay>
+where
+    Interface: RegisterInterface<AddressType = u8, Error = RtcError<E>>,
+    Delay: DelayNs,
+    E: embedded_hal::i2c::Error,
+{
+    type AddressType = u8;
+    type Error = RtcError<E>;
+    async fn read_register(
+        &mut self,
+        address: u8,
+        size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let mut attempt = 1;
+        let mut backoff_us = self.policy.backoff_us;
+        loop {
+            match self.interface.read_register(address, size_bits, data).await {
+                Err(e) if e.is_retryable() && attempt < self.policy.attempts => {
+                    warn!(
+                        "PCF8563: read reg {:#04x} failed, retrying ({}/{})",
+                        address, attempt, self.policy.attempts
+                    );
+                    self.retries = self.retries.saturating_add(1);
+                    attempt += 1;
+                    self.delay.delay_us(backoff_us).await;
+                    backoff_us = backoff_us.saturating_mul(self.policy.backoff_multiplier);
+                }
+                result => return result,
+            }
+        }
+    }
+    async fn write_register(
+        &mut self,
+        address: u8,
+        size_bits: u32,
+        data: &[u8],
+    ) -> Result<(), Self::Error> {
+        let mut attempt = 1;
+        let mut backoff_us = self.policy.backoff_us;
+        loop {
+            match self
+                .interface
+                .write_register(address, size_bits, data)
+                .await
+            {
+                Err(e) if e.is_retryable() && attempt < self.policy.attempts => {
+                    warn!(
+                        "PCF8563: write reg {:#04x} failed, retrying ({}/{})",
+                        address, attempt, self.policy.attempts
+                    );
+                    self.retries = self.retries.saturating_add(1);
+                    attempt += 1;
+                    self.delay.delay_us(backoff_us).await;
+                    backoff_us = backoff_us.saturating_mul(self.policy.backoff_multiplier);
+                }
+                result => return result,
+            }
+        }
     }
 }
 
@@ -53,20 +136,87 @@ pub struct Pcf8563<
     I2CBusErr: core::fmt::Debug,
 > {
     pub ll: Pcf8563LowLevel<I2CImpl>,
+    read_mode: ReadMode,
+    subsec_anchor: Option<SubsecAnchor>,
     _marker: core::marker::PhantomData<I2CBusErr>,
 }
 
 impl<I2CBus, E> Pcf8563<Pcf8563Interface<I2CBus>, E>
 where
     I2CBus: I2c<Error = E>,
-    E: core::fmt::Debug,
+    E: embedded_hal::i2c::Error,
 {
     pub fn new(i2c: I2CBus) -> Self {
+        Self::from_interface(Pcf8563Interface::new(i2c))
+    }
+
+    /// Create a driver for a device at a non-default 7-bit I2C address
+    pub fn new_with_address(i2c: I2CBus, address: u8) -> Self {
+        Self::from_interface(Pcf8563Interface::new_with_address(i2c, address))
+    }
+
+    /// Destroy the driver and return the I2C bus
+    pub fn release(self) -> I2CBus {
+        self.into_interface().release()
+    }
+}
+
+impl<I2CBus, D, E> Pcf8563<RetryInterface<Pcf8563Interface<I2CBus>, D>, E>
+where
+    I2CBus: I2c<Error = E>,
+    D: DelayNs,
+    E: embedded_hal::i2c::Error,
+{
+    /// Create a driver that retries transient I2C errors according to `policy`
+    ///
+    /// The retry count is available through
+    /// `rtc.interface().retries()`.
+    pub fn new_with_retry(i2c: I2CBus, delay: D, policy: RetryPolicy) -> Self {
+        Self::from_interface(RetryInterface::new(
+            Pcf8563Interface::new(i2c),
+            delay,
+            policy,
+        ))
+    }
+}
+
+impl<I2CImpl, I2CBusErr> Pcf8563<I2CImpl, I2CBusErr>
+where
+    I2CImpl: RegisterInterface<AddressType = u8, Error = RtcError<I2CBusErr>>,
+    I2CBusErr: core::fmt::Debug,
+{
+    /// Create a driver on top of any register interface
+    ///
+    /// Useful for wrapping [`Pcf8563Interface`] (e.g. for instrumentation)
+    /// or for custom transports.
+    pub fn from_interface(interface: I2CImpl) -> Self {
         Self {
-            ll: Pcf8563LowLevel::new(Pcf8563Interface::new(i2c)),
+            ll: Pcf8563LowLevel::new(interface),
+            read_mode: ReadMode::Burst,
+            subsec_anchor: None,
             _marker: core::marker::PhantomData,
         }
     }
+
+    /// Select how `get_datetime()` guards against a carry during the read
+    pub fn set_read_mode(&mut self, mode: ReadMode) {
+        self.read_mode = mode;
+    }
+
+    /// Current read mode
+    pub fn read_mode(&self) -> ReadMode {
+        self.read_mode
+    }
+
+    /// Mutable access to the underlying register interface
+    pub fn interface(&mut self) -> &mut I2CImpl {
+        self.ll.interface()
+    }
+
+    /// Destroy the driver and return the underlying register interface
+    pub fn into_interface(self) -> I2CImpl {
+        self.ll.interface
+    }
 }
 
 include!("bisync_helpers.rs");
@@ -83,9 +233,59 @@ where
     /// Get the current date and time
     ///
     /// Reads all 7 time/date registers (0x02-0x08) in a single I2C burst read,
-    /// as recommended by the datasheet to ensure consistency.
+    /// as recommended by the datasheet to ensure consistency. If the burst may
+    /// be split on your bus, select a different [`ReadMode`] with
+    /// [`set_read_mode`](Self::set_read_mode).
+    ///
+    /// Invalid BCD or out-of-range values (e.g. after a brown-out) are
+    /// rejected with [`RtcError::InvalidRegisterValue`]; use
+    /// [`get_datetime_unchecked`](Self::get_datetime_unchecked) to inspect them.
     #[bisync]
     pub async fn get_datetime(&mut self) -> Result<DateTime, RtcError<I2CBusErr>> {
+        let buf = self.read_datetime_registers().await?;
+        let dt = decode_datetime_checked(&buf)?;
+        debug!("PCF8563: get_datetime {:?}", dt);
+        Ok(dt)
+    }
+
+    /// Get the current date and time without validating the registers
+    ///
+    /// Decodes whatever the registers hold, so fields may be out of range
+    /// (e.g. minute 75). Intended for diagnostics.
+    #[bisync]
+    pub async fn get_datetime_unchecked(&mut self) -> Result<DateTime, RtcError<I2CBusErr>> {
+        let buf = self.read_datetime_registers().await?;
+        let dt = decode_datetime(&buf);
+        debug!("PCF8563: get_datetime_unchecked {:?}", dt);
+        Ok(dt)
+    }
+
+    /// Get the current date and time, verified by two matching reads
+    ///
+    /// Always uses [`ReadMode::DoubleRead`] regardless of the configured read
+    /// mode, and rejects invalid register contents like
+    /// [`get_datetime`](Self::get_datetime). Intended for safety-critical
+    /// timestamps.
+    #[bisync]
+    pub async fn get_datetime_verified(&mut self) -> Result<DateTime, RtcError<I2CBusErr>> {
+        let buf = self
+            .read_datetime_registers_with(ReadMode::DoubleRead)
+            .await?;
+        let dt = decode_datetime_checked(&buf)?;
+        debug!("PCF8563: get_datetime_verified {:?}", dt);
+        Ok(dt)
+    }
+
+    #[bisync]
+    async fn read_datetime_registers(&mut self) -> Result<[u8; 7], RtcError<I2CBusErr>> {
+        self.read_datetime_registers_with(self.read_mode).await
+    }
+
+    #[bisync]
+    async fn read_datetime_registers_with(
+        &mut self,
+        mode: ReadMode,
+    ) -> Result<[u8; 7], RtcError<I2CBusErr>> {
         // Bulk read registers 0x02-0x08 (7 bytes) in one I2C transaction:
         // buf[0]: Seconds (+ VL flag in bit 7)
         // buf[1]: Minutes
@@ -96,16 +296,47 @@ where
         // buf[6]: Years
         let mut buf = [0u8; 7];
         self.ll.interface().read_register(0x02, 0, &mut buf).await?;
+        if mode == ReadMode::Burst {
+            return Ok(buf);
+        }
 
-        Ok(DateTime {
-            seconds: bcd_to_dec(buf[0] & 0x7F), // mask VL flag
-            minutes: bcd_to_dec(buf[1] & 0x7F),
-            hours: bcd_to_dec(buf[2] & 0x3F),
-            day: bcd_to_dec(buf[3] & 0x3F),
-            weekday: buf[4] & 0x07,
-            month: bcd_to_dec(buf[5] & 0x1F), // mask century flag
-            year: bcd_to_dec(buf[6]),
-        })
+        for _ in 1..ReadMode::MAX_ATTEMPTS {
+            let consistent = match mode {
+                ReadMode::DoubleRead => {
+                    let mut again = [0u8; 7];
+                    self.ll
+                        .interface()
+                        .read_register(0x02, 0, &mut again)
+                        .await?;
+                    let consistent = again == buf;
+                    buf = again;
+                    consistent
+                }
+                _ => {
+                    // A carry anywhere in the burst changed the seconds first
+                    let mut seconds = [0u8; 1];
+                    self.ll
+                        .interface()
+                        .read_register(0x02, 0, &mut seconds)
+                        .await?;
+                    let consistent = (seconds[0] ^ buf[0]) & 0x7F == 0;
+                    if !consistent {
+                        self.ll.interface().read_register(0x02, 0, &mut buf).await?;
+                    }
+                    consistent
+                }
+            };
+            if consistent {
+                return Ok(buf);
+            }
+            debug!("PCF8563: time registers changed during read, retrying");
+        }
+
+        warn!(
+            "PCF8563: time registers inconsistent after {} reads",
+            ReadMode::MAX_ATTEMPTS
+        );
+        Err(RtcError::InconsistentRead)
     }
 
     /// Set the date and time
@@ -115,18 +346,15 @@ where
     #[bisync]
     pub async fn set_datetime(&mut self, dt: &DateTime) -> Result<(), RtcError<I2CBusErr>> {
         // Validate input
-        if dt.year > 99
-            || dt.month < 1
-            || dt.month > 12
-            || dt.weekday > 6
-            || dt.day < 1
-            || dt.day > 31
-            || dt.hours > 23
-            || dt.minutes > 59
-            || dt.seconds > 59
-        {
-            return Err(RtcError::InvalidInputData);
-        }
+        check_field(Field::Year, dt.year, 0..=99)?;
+        check_field(Field::Month, dt.month, 1..=12)?;
+        check_field(Field::Weekday, dt.weekday, 0..=6)?;
+        check_field(Field::Day, dt.day, 1..=31)?;
+        check_field(Field::Hours, dt.hours, 0..=23)?;
+        check_field(Field::Minutes, dt.minutes, 0..=59)?;
+        check_field(Field::Seconds, dt.seconds, 0..=59)?;
+
+        debug!("PCF8563: set_datetime {:?}", dt);
 
         // Bulk write registers 0x02-0x08 (7 bytes) in one I2C transaction
         let buf = [
@@ -138,6 +366,7 @@ where
             dec_to_bcd(dt.month), // preserves century flag as 0
             dec_to_bcd(dt.year),
         ];
+        self.subsec_anchor = None;
         self.ll.interface().write_register(0x02, 0, &buf).await?;
 
         Ok(())
@@ -146,9 +375,10 @@ where
     /// Set only the time (hours, minutes, seconds)
     #[bisync]
     pub async fn set_time(&mut self, time: &Time) -> Result<(), RtcError<I2CBusErr>> {
-        if time.hours > 23 || time.minutes > 59 || time.seconds > 59 {
-            return Err(RtcError::InvalidInputData);
-        }
+        check_field(Field::Hours, time.hours, 0..=23)?;
+        check_field(Field::Minutes, time.minutes, 0..=59)?;
+        check_field(Field::Seconds, time.seconds, 0..=59)?;
+        self.subsec_anchor = None;
 
         let seconds_bcd = dec_to_bcd(time.seconds);
         let minutes_bcd = dec_to_bcd(time.minutes);
@@ -179,6 +409,346 @@ where
         Ok(())
     }
 
+    /// Get the current date and time with millisecond resolution
+    ///
+    /// Syncs to an RTC seconds increment by polling register 0x02 and records
+    /// the `mono` tick count at that instant; later calls extend the RTC
+    /// reading with the ticks elapsed since. The anchor is refreshed every
+    /// [`SUBSEC_RESYNC_SECS`], so a sync (which blocks for up to 1 s) only
+    /// happens occasionally. Resolution is limited by the duration of one
+    /// register read.
+    ///
+    /// Returns [`RtcError::ClockNotRunning`] if no increment is seen within
+    /// 1.1 s and [`RtcError::InvalidTickRate`] if `mono` reports 0 Hz.
+    #[bisync]
+    pub async fn get_datetime_with_subsec<M: Monotonic>(
+        &mut self,
+        mono: &mut M,
+    ) -> Result<SubsecDateTime, RtcError<I2CBusErr>> {
+        let hz = mono.tick_hz() as u64;
+        if hz == 0 {
+            return Err(RtcError::InvalidTickRate);
+        }
+        let anchor = match self.subsec_anchor {
+            Some(anchor)
+                if mono.now().wrapping_sub(anchor.ticks) < SUBSEC_RESYNC_SECS as u64 * hz =>
+            {
+                anchor
+            }
+            _ => self.sync_subsec_anchor(mono).await?,
+        };
+
+        let buf = self.read_datetime_registers().await?;
+        let elapsed = mono.now().wrapping_sub(anchor.ticks);
+        let datetime = decode_datetime_checked(&buf)?;
+
+        // Whole seconds must agree; otherwise the MCU clock drifted across a
+        // boundary, so clamp and re-sync on the next call
+        let rtc_secs = ((datetime.seconds + 60 - anchor.seconds) % 60) as u64;
+        let millis = match (elapsed / hz).cmp(&rtc_secs) {
+            core::cmp::Ordering::Equal => ((elapsed % hz) * 1000 / hz) as u16,
+            core::cmp::Ordering::Greater => {
+                self.subsec_anchor = None;
+                999
+            }
+            core::cmp::Ordering::Less => {
+                self.subsec_anchor = None;
+                0
+            }
+        };
+
+        let result = SubsecDateTime { datetime, millis };
+        trace!("PCF8563: get_datetime_with_subsec {:?}", result);
+        Ok(result)
+    }
+
+    #[bisync]
+    async fn sync_subsec_anchor<M: Monotonic>(
+        &mut self,
+        mono: &mut M,
+    ) -> Result<SubsecAnchor, RtcError<I2CBusErr>> {
+        let timeout = mono.tick_hz() as u64 * 11 / 10;
+        let start = mono.now();
+        let mut reg = [0u8; 1];
+        self.ll.interface().read_register(0x02, 0, &mut reg).await?;
+        let first = reg[0] & 0x7F;
+
+        loop {
+            let ticks = mono.now();
+            self.ll.interface().read_register(0x02, 0, &mut reg).await?;
+            if reg[0] & 0x7F != first {
+                let anchor = SubsecAnchor {
+                    ticks,
+                    seconds: decode_seconds_checked(reg[0])?,
+                };
+                self.subsec_anchor = Some(anchor);
+                debug!("PCF8563: seconds increment at tick {}", ticks);
+                return Ok(anchor);
+            }
+            if ticks.wrapping_sub(start) > timeout {
+                warn!("PCF8563: seconds did not advance, clock not running");
+                return Err(RtcError::ClockNotRunning);
+            }
+        }
+    }
+
+    // =========================================================================
+    // External Time Sources
+    // =========================================================================
+
+    /// Set the RTC from a trusted reference if it is off by more than `threshold_secs`
+    ///
+    /// The RTC is always set if its voltage-low flag is set or it holds
+    /// invalid registers. A threshold of 0 sets it on any difference.
+    #[bisync]
+    pub async fn sync_datetime(
+        &mut self,
+        reference: &DateTime,
+        threshold_secs: u32,
+    ) -> Result<SyncOutcome, RtcError<I2CBusErr>> {
+        let offset_secs = self.offset_from(reference).await?;
+        if let Some(offset_secs) = offset_secs
+            && offset_secs.unsigned_abs() <= threshold_secs as u64
+        {
+            return Ok(SyncOutcome::InSync { offset_secs });
+        }
+
+        debug!("PCF8563: syncing to reference, offset {:?} s", offset_secs);
+        self.set_datetime(reference).await?;
+        Ok(SyncOutcome::Updated { offset_secs })
+    }
+
+    /// RTC reading minus `reference` in seconds, or `None` if the RTC holds no valid time
+    #[bisync]
+    async fn offset_from(
+        &mut self,
+        reference: &DateTime,
+    ) -> Result<Option<i64>, RtcError<I2CBusErr>> {
+        let buf = self.read_datetime_registers().await?;
+        Ok(match decode_datetime_checked::<I2CBusErr>(&buf) {
+            Ok(current) if buf[0] & 0x80 == 0 => {
+                Some(current.to_unix_timestamp() - reference.to_unix_timestamp())
+            }
+            _ => None,
+        })
+    }
+
+    /// Set the RTC from a GPS `$xxRMC` or `$xxZDA` sentence
+    ///
+    /// Only sets the time when the sentence has a valid checksum and fix and
+    /// the RTC is off by more than `threshold_secs`; see [`parse_nmea`] and
+    /// [`sync_datetime`](Self::sync_datetime). Other sentence types are
+    /// reported as [`NmeaError::Unsupported`] so a whole NMEA stream can be
+    /// fed through.
+    #[bisync]
+    pub async fn sync_from_nmea(
+        &mut self,
+        sentence: &str,
+        threshold_secs: u32,
+    ) -> Result<SyncOutcome, SyncError<I2CBusErr, NmeaError>> {
+        let reference = parse_nmea(sentence).map_err(SyncError::Source)?;
+        self.sync_datetime(&reference, threshold_secs)
+            .await
+            .map_err(SyncError::Rtc)
+    }
+
+    /// Start an SNTP exchange, stamping the request from the local clock
+    ///
+    /// Local time is the RTC reading extended with `mono` (see
+    /// [`get_datetime_with_subsec`](Self::get_datetime_with_subsec)); if the
+    /// RTC holds no valid time, 2000-01-01 is used as the local base. Send
+    /// [`SntpRequest::packet`] right away and record `mono.now()` when the
+    /// reply arrives.
+    #[bisync]
+    pub async fn sntp_request<M: Monotonic>(
+        &mut self,
+        mono: &mut M,
+    ) -> Result<SntpRequest, RtcError<I2CBusErr>> {
+        let base_micros = match self.get_datetime_with_subsec(mono).await {
+            Ok(ts) => ts.datetime.to_unix_timestamp() * 1_000_000 + ts.millis as i64 * 1000,
+            Err(RtcError::InvalidRegisterValue { .. } | RtcError::ClockNotRunning) => {
+                DateTime::default().to_unix_timestamp() * 1_000_000
+            }
+            Err(e) => return Err(e),
+        };
+        // One reading serves as both the local-clock base and T1, so the
+        // stamp in the packet matches the mapping used for T4
+        let base_ticks = mono.now();
+        Ok(SntpRequest::new(base_micros, base_ticks, mono.tick_hz()))
+    }
+
+    /// Set the RTC from an SNTP reply to `request`
+    ///
+    /// Validates the reply (see [`process_sntp_response`](crate::process_sntp_response)) and
+    /// derives the current time from the local clock and the measured
+    /// offset. If the RTC is off by more than `threshold_secs` (or holds no
+    /// valid time), it is set in phase with the server's second boundary via
+    /// [`set_datetime_at`](Self::set_datetime_at), so `mono` and `delay`
+    /// should be the ones the request was stamped with. Returns the
+    /// measurement alongside the outcome, e.g. for logging the delay.
+    #[bisync]
+    pub async fn sync_from_sntp<M: Monotonic, D: DelayNs>(
+        &mut self,
+        request: &SntpRequest,
+        response: &[u8],
+        received_ticks: u64,
+        mono: &mut M,
+        delay: &mut D,
+        threshold_secs: u32,
+    ) -> Result<(SyncOutcome, SntpMeasurement), SyncError<I2CBusErr, SntpError>> {
+        let measurement = request
+            .process(response, received_ticks)
+            .map_err(SyncError::Source)?;
+        debug!("PCF8563: SNTP {:?}", measurement);
+
+        // Server time at the last whole second and the MCU tick it fell on
+        let now_ticks = mono.now();
+        let now_micros = request.local_micros_at(now_ticks) + measurement.offset_micros;
+        let into_second = now_micros.rem_euclid(1_000_000) as u64;
+        let boundary_ticks = now_ticks
+            .wrapping_sub((into_second as u128 * mono.tick_hz() as u128 / 1_000_000) as u64);
+        let reference = DateTime::from_unix_timestamp(now_micros.div_euclid(1_000_000))
+            .ok_or(SyncError::Source(SntpError::OutOfRange))?;
+
+        let offset_secs = self.offset_from(&reference).await.map_err(SyncError::Rtc)?;
+        if let Some(offset_secs) = offset_secs
+            && offset_secs.unsigned_abs() <= threshold_secs as u64
+        {
+            return Ok((SyncOutcome::InSync { offset_secs }, measurement));
+        }
+
+        debug!("PCF8563: syncing to SNTP, offset {:?} s", offset_secs);
+        self.set_datetime_at(&reference, boundary_ticks, mono, delay)
+            .await
+            .map_err(SyncError::Rtc)?;
+        Ok((SyncOutcome::Updated { offset_secs }, measurement))
+    }
+
+    // =========================================================================
+    // Synchronized Time Setting (STOP bit)
+    // =========================================================================
+
+    /// Stop the clock and load the date and time for a synchronized start
+    ///
+    /// Sets STOP, which holds the divider chain in reset, and writes `dt`,
+    /// the time at the upcoming second boundary. The clock stays stopped
+    /// until [`commit_on_edge`](Self::commit_on_edge) or
+    /// [`commit_now`](Self::commit_now) releases it.
+    #[bisync]
+    pub async fn prepare_set_datetime(&mut self, dt: &DateTime) -> Result<(), RtcError<I2CBusErr>> {
+        let mut op = self.ll.control_status_1();
+        modify_internal(&mut op, |r| r.set_stop(true)).await?;
+        self.set_datetime(dt).await
+    }
+
+    /// Start the prepared clock in phase with a second-boundary edge
+    ///
+    /// Waits up to `timeout_us` for a rising edge on `pin` (e.g. a GPS PPS
+    /// output), delays by [`STOP_RELEASE_DELAY_US`] and releases STOP, so the
+    /// first increment lands on the next boundary. The remaining error is
+    /// dominated by the `delay` accuracy and the I2C write latency. On
+    /// [`EdgeError::Timeout`] the clock stays stopped.
+    #[bisync]
+    pub async fn commit_on_edge<P: EdgeInput, D: DelayNs>(
+        &mut self,
+        pin: &mut P,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Result<(), EdgeError<I2CBusErr, P::Error>> {
+        // Read beforehand so only a single write follows the edge
+        let mut op = self.ll.control_status_1();
+        let mut reg = read_internal(&mut op).await.map_err(EdgeError::Rtc)?;
+        reg.set_stop(false);
+
+        if !wait_for_rising_edge_internal(pin, delay, timeout_us)
+            .await
+            .map_err(EdgeError::Pin)?
+        {
+            return Err(EdgeError::Timeout);
+        }
+        delay.delay_us(STOP_RELEASE_DELAY_US).await;
+        self.subsec_anchor = None;
+
+        let mut op = self.ll.control_status_1();
+        write_internal(&mut op, |r| *r = reg)
+            .await
+            .map_err(EdgeError::Rtc)
+    }
+
+    /// Start the prepared clock immediately
+    ///
+    /// For an externally timed instant (e.g. NTP-derived), call this
+    /// [`STOP_RELEASE_DELAY_US`] after the second boundary that `dt` in
+    /// [`prepare_set_datetime`](Self::prepare_set_datetime) refers to.
+    #[bisync]
+    pub async fn commit_now(&mut self) -> Result<(), RtcError<I2CBusErr>> {
+        self.set_clock_running(true).await
+    }
+
+    /// Set the date and time in phase with a second boundary observed earlier
+    ///
+    /// `dt` is the time at the boundary, which happened at `boundary_ticks`
+    /// of `mono`. The clock is prepared with the time of the next boundary
+    /// that leaves enough headroom for the register writes and released
+    /// [`STOP_RELEASE_DELAY_US`] after it, so the remaining error is the
+    /// edge timestamping plus the `delay` accuracy. Returns
+    /// [`RtcError::InvalidTickRate`] if `mono` reports 0 Hz.
+    #[bisync]
+    pub async fn set_datetime_at<M: Monotonic, D: DelayNs>(
+        &mut self,
+        dt: &DateTime,
+        boundary_ticks: u64,
+        mono: &mut M,
+        delay: &mut D,
+    ) -> Result<(), RtcError<I2CBusErr>> {
+        // Headroom for the register writes before STOP is released
+        const MARGIN_US: u64 = 5_000;
+
+        let hz = mono.tick_hz() as u128;
+        if hz == 0 {
+            return Err(RtcError::InvalidTickRate);
+        }
+        let elapsed_us =
+            |now: u64| (now.wrapping_sub(boundary_ticks) as u128 * 1_000_000 / hz) as u64;
+        let release_delay = STOP_RELEASE_DELAY_US as u64;
+
+        let seconds = (elapsed_us(mono.now()) + MARGIN_US)
+            .saturating_sub(release_delay)
+            .div_ceil(1_000_000);
+        // Only the step past 2099-12-31 23:59:59 can leave the range
+        let target = DateTime::from_unix_timestamp(dt.to_unix_timestamp() + seconds as i64).ok_or(
+            RtcError::InvalidField {
+                field: Field::Year,
+                value: 100,
+            },
+        )?;
+        self.prepare_set_datetime(&target).await?;
+
+        let release_at = seconds * 1_000_000 + release_delay;
+        if let Some(wait) = release_at.checked_sub(elapsed_us(mono.now())) {
+            delay.delay_us(wait as u32).await;
+        }
+        self.commit_now().await
+    }
+
+    /// Set the RTC from a decoded DCF77/MSF minute frame
+    ///
+    /// Uses [`set_datetime_at`](Self::set_datetime_at) with the frame's
+    /// minute mark, so call it right after
+    /// [`RadioClockDecoder::edge`](crate::RadioClockDecoder::edge) returned
+    /// the frame, with the same `mono` that timestamped the edges.
+    #[bisync]
+    pub async fn sync_from_radio<M: Monotonic, D: DelayNs>(
+        &mut self,
+        time: &RadioTime,
+        mono: &mut M,
+        delay: &mut D,
+    ) -> Result<(), RtcError<I2CBusErr>> {
+        debug!("PCF8563: radio time {:?}", time);
+        self.set_datetime_at(&time.utc, time.minute_start_ticks, mono, delay)
+            .await
+    }
+
     // =========================================================================
     // Clock Integrity (Voltage Low Detection)
     // =========================================================================
@@ -203,6 +773,72 @@ where
         modify_internal(&mut op, |r| r.set_vl(false)).await
     }
 
+    /// Set the RTC to `build_time` if it holds no trustworthy time
+    ///
+    /// Meant for first boot after flashing: the time is set if the VL flag
+    /// is set, the registers are invalid, or the stored time is earlier than
+    /// `build_time` (usually [`build_time!()`](crate::build_time)). Returns
+    /// whether the time was set.
+    #[bisync]
+    pub async fn init_from_build_time_if_invalid(
+        &mut self,
+        build_time: &DateTime,
+    ) -> Result<bool, RtcError<I2CBusErr>> {
+        let buf = self.read_datetime_registers().await?;
+        if buf[0] & 0x80 == 0
+            && let Ok(current) = decode_datetime_checked::<I2CBusErr>(&buf)
+            && current.to_unix_timestamp() >= build_time.to_unix_timestamp()
+        {
+            return Ok(false);
+        }
+
+        info!("PCF8563: clock invalid or behind build time, setting it");
+        self.set_datetime(build_time).await?;
+        Ok(true)
+    }
+
+    /// Assess clock health at boot
+    ///
+    /// Combines the VL flag, the STOP bit, TEST1/TESTC, BCD validity and
+    /// calendar plausibility of the time registers from a single burst read
+    /// into a [`ClockHealth`] report with a recommended action. Unlike
+    /// [`is_clock_valid`](Self::is_clock_valid), this also catches a stopped
+    /// oscillator and garbage register contents.
+    #[bisync]
+    pub async fn check_health(&mut self) -> Result<ClockHealth, RtcError<I2CBusErr>> {
+        let snapshot = self.read_snapshot().await?;
+        let health = ClockHealth::assess(&snapshot, None);
+        debug!("PCF8563: check_health {:?}", health);
+        Ok(health)
+    }
+
+    /// Assess clock health, including a check that the seconds advance
+    ///
+    /// Like [`check_health`](Self::check_health), but additionally waits
+    /// 1.1 s with `delay` and verifies that the seconds register changed.
+    /// The wait is skipped if the STOP bit is set.
+    #[bisync]
+    pub async fn check_health_with_delay<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<ClockHealth, RtcError<I2CBusErr>> {
+        let snapshot = self.read_snapshot().await?;
+        let seconds_advanced = if snapshot.control_status_1().stop() {
+            None
+        } else {
+            delay.delay_ms(1100).await;
+            let mut seconds = [0u8; 1];
+            self.ll
+                .interface()
+                .read_register(0x02, 0, &mut seconds)
+                .await?;
+            Some((seconds[0] ^ snapshot.raw()[0x02]) & 0x7F != 0)
+        };
+        let health = ClockHealth::assess(&snapshot, seconds_advanced);
+        debug!("PCF8563: check_health {:?}", health);
+        Ok(health)
+    }
+
     // =========================================================================
     // Century Flag
     // =========================================================================
@@ -231,6 +867,7 @@ where
     /// Start or stop the RTC clock
     #[bisync]
     pub async fn set_clock_running(&mut self, running: bool) -> Result<(), RtcError<I2CBusErr>> {
+        self.subsec_anchor = None;
         let mut op = self.ll.control_status_1();
         modify_internal(&mut op, |r| r.set_stop(!running)).await
     }
@@ -243,53 +880,91 @@ where
         Ok(!reg.stop())
     }
 
+    // =========================================================================
+    // Test Modes (TEST1 / TESTC)
+    // =========================================================================
+
+    /// Enable the Power-On Reset (POR) override facility (TESTC = 1)
+    ///
+    /// This only arms the facility; entering the override mode additionally
+    /// requires the SDA/SCL toggling sequence described in the datasheet.
+    #[bisync]
+    pub async fn enable_por_override(&mut self) -> Result<(), RtcError<I2CBusErr>> {
+        let mut op = self.ll.control_status_1();
+        modify_internal(&mut op, |r| r.set_testc(PorOverride::Enabled)).await
+    }
+
+    /// Disable the Power-On Reset (POR) override facility (TESTC = 0)
+    ///
+    /// This is the required setting for normal operation.
+    #[bisync]
+    pub async fn disable_por_override(&mut self) -> Result<(), RtcError<I2CBusErr>> {
+        let mut op = self.ll.control_status_1();
+        modify_internal(&mut op, |r| r.set_testc(PorOverride::Disabled)).await
+    }
+
+    /// Get the state of the Power-On Reset (POR) override facility
+    #[bisync]
+    pub async fn get_por_override(&mut self) -> Result<PorOverride, RtcError<I2CBusErr>> {
+        let mut op = self.ll.control_status_1();
+        let reg = read_internal(&mut op).await?;
+        Ok(reg.testc())
+    }
+
+    /// Check that TEST1 and TESTC are in their normal-operation state
+    ///
+    /// Logs a warning if EXT_CLK test mode is active or the POR override
+    /// facility is still enabled (e.g. left over from board bring-up or
+    /// after a power-on reset without calling [`init`](Self::init)).
+    #[bisync]
+    pub async fn check_test_modes(&mut self) -> Result<TestModeStatus, RtcError<I2CBusErr>> {
+        let mut op = self.ll.control_status_1();
+        let reg = read_internal(&mut op).await?;
+        let status = TestModeStatus {
+            ext_clk_test_mode: reg.test1(),
+            por_override: reg.testc(),
+        };
+
+        if status.ext_clk_test_mode {
+            warn!("PCF8563: TEST1 is set, EXT_CLK test mode is active");
+        }
+        if status.por_override == PorOverride::Enabled {
+            warn!("PCF8563: TESTC is set, POR override facility is enabled");
+        }
+
+        Ok(status)
+    }
+
     // =========================================================================
     // Alarm
     // =========================================================================
 
     /// Get the current alarm configuration
+    ///
+    /// Reads all 4 alarm registers (0x09-0x0C) in a single I2C burst read.
+    /// Enabled components holding invalid BCD or out-of-range values are
+    /// rejected with [`RtcError::InvalidRegisterValue`].
     #[bisync]
     pub async fn get_alarm(&mut self) -> Result<Alarm, RtcError<I2CBusErr>> {
-        let mut op_min = self.ll.minute_alarm();
-        let min_reg = read_internal(&mut op_min).await?;
-
-        let mut op_hr = self.ll.hour_alarm();
-        let hr_reg = read_internal(&mut op_hr).await?;
+        let mut buf = [0u8; 4];
+        self.ll.interface().read_register(0x09, 0, &mut buf).await?;
 
-        let mut op_day = self.ll.day_alarm();
-        let day_reg = read_internal(&mut op_day).await?;
+        let alarm = decode_alarm_checked(&buf)?;
+        debug!("PCF8563: get_alarm {:?}", alarm);
+        Ok(alarm)
+    }
 
-        let mut op_wd = self.ll.weekday_alarm();
-        let wd_reg = read_internal(&mut op_wd).await?;
+    /// Get the alarm configuration without validating the registers
+    ///
+    /// Intended for diagnostics; enabled components may be out of range.
+    #[bisync]
+    pub async fn get_alarm_unchecked(&mut self) -> Result<Alarm, RtcError<I2CBusErr>> {
+        let mut buf = [0u8; 4];
+        self.ll.interface().read_register(0x09, 0, &mut buf).await?;
 
-        Ok(Alarm {
-            minute: if min_reg.ae_m() {
-                None
-            } else {
-                Some(bcd_to_dec(
-                    (min_reg.minute_alarm_ten() << 4) as u8 | min_reg.minute_alarm_unit() as u8,
-                ))
-            },
-            hour: if hr_reg.ae_h() {
-                None
-            } else {
-                Some(bcd_to_dec(
-                    (hr_reg.hour_alarm_ten() << 4) as u8 | hr_reg.hour_alarm_unit() as u8,
-                ))
-            },
-            day: if day_reg.ae_d() {
-                None
-            } else {
-                Some(bcd_to_dec(
-                    (day_reg.day_alarm_ten() << 4) as u8 | day_reg.day_alarm_unit() as u8,
-                ))
-            },
-            weekday: if wd_reg.ae_w() {
-                None
-            } else {
-                Some(wd_reg.weekday_alarm() as u8)
-            },
-        })
+        let alarm = decode_alarm(&buf);
+        debug!("PCF8563: get_alarm_unchecked {:?}", alarm);
+        Ok(alarm)
     }
 
     /// Set the alarm configuration
@@ -298,6 +973,21 @@ where
     /// or `None` to disable it.
     #[bisync]
     pub async fn set_alarm(&mut self, alarm: &Alarm) -> Result<(), RtcError<I2CBusErr>> {
+        if let Some(min) = alarm.minute {
+            check_field(Field::MinuteAlarm, min, 0..=59)?;
+        }
+        if let Some(hr) = alarm.hour {
+            check_field(Field::HourAlarm, hr, 0..=23)?;
+        }
+        if let Some(day) = alarm.day {
+            check_field(Field::DayAlarm, day, 1..=31)?;
+        }
+        if let Some(wd) = alarm.weekday {
+            check_field(Field::WeekdayAlarm, wd, 0..=6)?;
+        }
+
+        debug!("PCF8563: set_alarm {:?}", alarm);
+
         // Minute alarm
         let mut op_min = self.ll.minute_alarm();
         write_internal(&mut op_min, |r| {
@@ -407,7 +1097,7 @@ where
     pub async fn get_timer_value(&mut self) -> Result<u8, RtcError<I2CBusErr>> {
         let mut op = self.ll.timer();
         let reg = read_internal(&mut op).await?;
-        Ok(reg.timer_value() as u8)
+        Ok(reg.timer_value())
     }
 
     /// Set the timer source clock frequency
@@ -520,6 +1210,196 @@ where
         Ok(reg.fd())
     }
 
+    /// Configure CLKOUT from a frequency
+    ///
+    /// `None` disables the output. Frequencies other than 32768, 1024, 32
+    /// and 1 Hz are rejected with [`RtcError::InvalidField`].
+    #[cfg(feature = "fugit")]
+    #[bisync]
+    pub async fn set_clkout(
+        &mut self,
+        freq: Option<fugit::HertzU32>,
+    ) -> Result<(), RtcError<I2CBusErr>> {
+        let fd = match freq {
+            Some(freq) => {
+                Some(
+                    ClkoutFrequency::try_from(freq).map_err(|_| RtcError::InvalidField {
+                        field: Field::ClkoutFrequency,
+                        value: freq.raw(),
+                    })?,
+                )
+            }
+            None => None,
+        };
+
+        let mut op = self.ll.clkout_control();
+        modify_internal(&mut op, |r| {
+            r.set_fe(fd.is_some());
+            if let Some(fd) = fd {
+                r.set_fd(fd);
+            }
+        })
+        .await
+    }
+
+    /// Get the CLKOUT frequency, or `None` if the output is disabled
+    #[cfg(feature = "fugit")]
+    #[bisync]
+    pub async fn get_clkout(&mut self) -> Result<Option<fugit::HertzU32>, RtcError<I2CBusErr>> {
+        let mut op = self.ll.clkout_control();
+        let reg = read_internal(&mut op).await?;
+        Ok(reg.fe().then(|| reg.fd().to_hertz()))
+    }
+
+    /// Calibrate an MCU clock against the RTC crystal using CLKOUT
+    ///
+    /// Switches CLKOUT to `freq` (only [`ClkoutFrequency::Freq1Hz`] and
+    /// [`ClkoutFrequency::Freq1024Hz`] are supported), measures `edges` periods
+    /// (at least 1) with `capture` and restores the previous CLKOUT
+    /// configuration.
+    ///
+    /// The result is only as accurate as the RTC crystal itself (typically
+    /// ±20 ppm) and the edge timestamping; longer windows reduce the latter.
+    #[bisync]
+    pub async fn calibrate_mcu_clock<C: ClkoutCapture>(
+        &mut self,
+        capture: &mut C,
+        freq: ClkoutFrequency,
+        edges: u32,
+    ) -> Result<McuCalibration, CalibrationError<I2CBusErr, C::Error>> {
+        let clkout_hz: u64 = match freq {
+            ClkoutFrequency::Freq1Hz => 1,
+            ClkoutFrequency::Freq1024Hz => 1024,
+            _ => return Err(CalibrationError::UnsupportedFrequency),
+        };
+        if edges == 0 {
+            return Err(CalibrationError::InvalidEdgeCount);
+        }
+
+        let mut op = self.ll.clkout_control();
+        let saved = read_internal(&mut op)
+            .await
+            .map_err(CalibrationError::Rtc)?;
+        self.set_clkout_frequency(freq)
+            .await
+            .map_err(CalibrationError::Rtc)?;
+        self.set_clkout_enabled(true)
+            .await
+            .map_err(CalibrationError::Rtc)?;
+
+        let measured = capture.measure_edges(edges).await;
+
+        self.set_clkout_frequency(saved.fd())
+            .await
+            .map_err(CalibrationError::Rtc)?;
+        self.set_clkout_enabled(saved.fe())
+            .await
+            .map_err(CalibrationError::Rtc)?;
+
+        let measured_ticks = measured.map_err(CalibrationError::Capture)?;
+        let expected_scaled = edges as u64 * capture.tick_hz() as u64;
+        let diff = (measured_ticks * clkout_hz) as i64 - expected_scaled as i64;
+
+        Ok(McuCalibration {
+            ppm: diff as f32 * 1_000_000.0 / expected_scaled as f32,
+            edges,
+            measured_ticks,
+            expected_ticks: expected_scaled / clkout_hz,
+        })
+    }
+
+    // =========================================================================
+    // Register File
+    // =========================================================================
+
+    /// Read all 16 registers (0x00-0x0F) in a single I2C burst read
+    #[bisync]
+    pub async fn read_snapshot(&mut self) -> Result<RegisterSnapshot, RtcError<I2CBusErr>> {
+        let mut buf = [0u8; REGISTER_COUNT];
+        self.ll.interface().read_register(0x00, 0, &mut buf).await?;
+        Ok(RegisterSnapshot::from_raw(buf))
+    }
+
+    /// Log every register with its address, raw value and decoded fields
+    ///
+    /// Output goes through the `defmt`/`log` facade at info level; without
+    /// either feature only the snapshot is read and nothing is formatted. The
+    /// snapshot is returned for further use, e.g. `snapshot.dump()` for a
+    /// [`Display`](core::fmt::Display) rendering.
+    #[bisync]
+    pub async fn dump_registers(&mut self) -> Result<RegisterSnapshot, RtcError<I2CBusErr>> {
+        let snapshot = self.read_snapshot().await?;
+        log_registers(&snapshot);
+        Ok(snapshot)
+    }
+
+    /// Write a snapshot back to the device in a single I2C transaction
+    ///
+    /// This also restores the time registers, so the clock is set back to
+    /// the time at which the snapshot was taken.
+    #[bisync]
+    pub async fn restore_snapshot(
+        &mut self,
+        snapshot: &RegisterSnapshot,
+    ) -> Result<(), RtcError<I2CBusErr>> {
+        self.write_all_registers(snapshot.raw()).await
+    }
+
+    /// Write all 16 registers (0x00-0x0F) in a single I2C transaction
+    ///
+    /// Restores a complete device state, e.g. one previously captured with a
+    /// burst read. Note that writing 1 to AF/TF leaves those flags unchanged.
+    #[bisync]
+    pub async fn write_all_registers(
+        &mut self,
+        registers: &[u8; REGISTER_COUNT],
+    ) -> Result<(), RtcError<I2CBusErr>> {
+        self.subsec_anchor = None;
+        self.ll.interface().write_register(0x00, 0, registers).await
+    }
+
+    // =========================================================================
+    // Device Detection
+    // =========================================================================
+
+    /// Check that a PCF8563-compatible device is present
+    ///
+    /// Returns `Ok(false)` if nothing acknowledges the address, or if the
+    /// register file does not read back plausibly (see
+    /// [`RegisterSnapshot::is_plausible`]). Other bus errors are returned.
+    #[bisync]
+    pub async fn probe(&mut self) -> Result<bool, RtcError<I2CBusErr>> {
+        match self.read_snapshot().await {
+            Ok(snapshot) => {
+                let plausible = snapshot.is_plausible();
+                if !plausible {
+                    warn!("PCF8563: device at address does not read back plausibly");
+                }
+                Ok(plausible)
+            }
+            Err(RtcError::DeviceNotPresent(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Identify the chip as an NXP PCF8563 or a BM8563/clone
+    ///
+    /// Read-only: nothing is written to the device. Both parts document the
+    /// same register map and reset values, and no readback difference has
+    /// been measured on both chips yet, so this currently returns
+    /// [`Variant::Unknown`] for any device that answers. The Timer_control
+    /// bits 6-2, which both datasheets leave undefined, are logged at debug
+    /// level to help collect that evidence.
+    #[bisync]
+    pub async fn detect_variant(&mut self) -> Result<Variant, RtcError<I2CBusErr>> {
+        let snapshot = self.read_snapshot().await?;
+        debug!(
+            "PCF8563: Timer_control undefined bits {:#04x}, variant unknown",
+            snapshot.raw()[0x0E] & 0x7C
+        );
+        Ok(Variant::Unknown)
+    }
+
     // =========================================================================
     // Initialization
     // =========================================================================
@@ -532,12 +1412,14 @@ where
     /// - Sets timer to lowest frequency (1/60 Hz) for power saving
     #[bisync]
     pub async fn init(&mut self) -> Result<(), RtcError<I2CBusErr>> {
+        self.subsec_anchor = None;
+
         // Clear control status 1
         let mut op1 = self.ll.control_status_1();
         write_internal(&mut op1, |r| {
             r.set_test1(false);
             r.set_stop(false);
-            r.set_testc(false);
+            r.set_testc(PorOverride::Disabled);
         })
         .await?;
 
@@ -579,14 +1461,16 @@ where
         let century_flag = self.get_century_flag()?;
         let base_year = if century_flag { 1900 } else { 2000 };
 
+        // Month and BCD ranges are checked by get_datetime; only a day past
+        // the end of the month (e.g. 30 Feb) is left to reject here
         let date =
             RtccNaiveDate::from_ymd_opt(base_year + dt.year as i32, dt.month as u32, dt.day as u32)
-                .ok_or(RtcError::InvalidInputData)?;
-        let time =
-            RtccNaiveTime::from_hms_opt(dt.hours as u32, dt.minutes as u32, dt.seconds as u32)
-                .ok_or(RtcError::InvalidInputData)?;
+                .ok_or(RtcError::InvalidField {
+                    field: Field::Day,
+                    value: dt.day as u32,
+                })?;
 
-        Ok(RtccNaiveDateTime::new(date, time))
+        Ok(RtccNaiveDateTime::new(date, rtcc_time(&dt)?))
     }
 
     fn set_datetime(&mut self, datetime: &RtccNaiveDateTime) -> Result<(), Self::Error> {
@@ -594,9 +1478,7 @@ where
         let time = datetime.time();
         let year = date.year();
 
-        if !(1900..=2099).contains(&year) {
-            return Err(RtcError::InvalidInputData);
-        }
+        check_year(year)?;
 
         let dt = DateTime {
             year: (year % 100) as u8,
@@ -630,26 +1512,18 @@ where
 
     fn hours(&mut self) -> Result<RtccHours, Self::Error> {
         let hours = self.get_datetime()?.hours;
-        if hours > 23 {
-            Err(RtcError::InvalidInputData)
-        } else {
-            Ok(RtccHours::H24(hours))
-        }
+        check_field(Field::Hours, hours, 0..=23)?;
+        Ok(RtccHours::H24(hours))
     }
 
     fn time(&mut self) -> Result<RtccNaiveTime, Self::Error> {
-        let dt = self.get_datetime()?;
-        RtccNaiveTime::from_hms_opt(dt.hours as u32, dt.minutes as u32, dt.seconds as u32)
-            .ok_or(RtcError::InvalidInputData)
+        rtcc_time(&self.get_datetime()?)
     }
 
     fn weekday(&mut self) -> Result<u8, Self::Error> {
         let weekday = self.get_datetime()?.weekday;
-        if weekday > 6 {
-            Err(RtcError::InvalidInputData)
-        } else {
-            Ok(weekday + 1)
-        }
+        check_field(Field::Weekday, weekday, 0..=6)?;
+        Ok(weekday + 1)
     }
 
     fn day(&mut self) -> Result<u8, Self::Error> {
@@ -698,7 +1572,12 @@ where
                     h + 12
                 }
             }
-            _ => return Err(RtcError::InvalidInputData),
+            RtccHours::H24(h) | RtccHours::AM(h) | RtccHours::PM(h) => {
+                return Err(RtcError::InvalidField {
+                    field: Field::Hours,
+                    value: h as u32,
+                });
+            }
         };
 
         let mut dt = self.get_datetime()?;
@@ -716,9 +1595,7 @@ where
     }
 
     fn set_weekday(&mut self, weekday: u8) -> Result<(), Self::Error> {
-        if !(1..=7).contains(&weekday) {
-            return Err(RtcError::InvalidInputData);
-        }
+        check_field(Field::Weekday, weekday, 1..=7)?;
 
         let mut dt = self.get_datetime()?;
         dt.weekday = weekday - 1;
@@ -738,9 +1615,7 @@ where
     }
 
     fn set_year(&mut self, year: u16) -> Result<(), Self::Error> {
-        if !(1900..=2099).contains(&year) {
-            return Err(RtcError::InvalidInputData);
-        }
+        check_year(year as i32)?;
 
         let mut dt = self.get_datetime()?;
         dt.year = (year % 100) as u8;
@@ -750,9 +1625,7 @@ where
 
     fn set_date(&mut self, date: &RtccNaiveDate) -> Result<(), Self::Error> {
         let year = date.year();
-        if !(1900..=2099).contains(&year) {
-            return Err(RtcError::InvalidInputData);
-        }
+        check_year(year)?;
 
         let mut dt = self.get_datetime()?;
         dt.year = (year % 100) as u8;
@@ -764,3 +1637,30 @@ where
         self.set_datetime(&dt)
     }
 }
+
+/// Check that a full year is representable (1900-2099, via the century flag)
+#[cfg(feature = "rtcc")]
+#[only_sync]
+fn check_year<E>(year: i32) -> Result<(), RtcError<E>> {
+    if (1900..=2099).contains(&year) {
+        Ok(())
+    } else {
+        Err(RtcError::InvalidField {
+            field: Field::Year,
+            value: year.max(0) as u32,
+        })
+    }
+}
+
+#[cfg(feature = "rtcc")]
+#[only_sync]
+fn rtcc_time<E>(dt: &DateTime) -> Result<RtccNaiveTime, RtcError<E>> {
+    check_field(Field::Hours, dt.hours, 0..=23)?;
+    check_field(Field::Minutes, dt.minutes, 0..=59)?;
+    check_field(Field::Seconds, dt.seconds, 0..=59)?;
+    // All components were checked above
+    Ok(
+        RtccNaiveTime::from_hms_opt(dt.hours as u32, dt.minutes as u32, dt.seconds as u32)
+            .unwrap_or_default(),
+    )
+}
@@ -0,0 +1,222 @@
+use core::convert::Infallible;
+use core::fmt;
+
+use crate::field_sets::FieldSetValue;
+use crate::{
+    ClkoutFrequency, Pcf8563LowLevel, PorOverride, REGISTER_COUNT, RegisterSnapshot, TimerFrequency,
+};
+
+/// Human-readable dump of a [`RegisterSnapshot`], one register per line
+///
+/// Each line shows the address, the register name from `device.yaml`, the
+/// raw value and the decoded fields, with BCD digits combined and flags
+/// spelled out, e.g. `0x09 minute_alarm     = 0xB7 AE_M=disabled minute=37`.
+pub struct RegisterDump<'a>(pub &'a RegisterSnapshot);
+
+impl fmt::Display for RegisterDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let raw = self.0.raw();
+        let mut result = Ok(());
+        for_each_register(self.0, |address, name, value| {
+            let separator = if address > 0 { "\n" } else { "" };
+            result = result.and_then(|()| {
+                write!(
+                    f,
+                    "{separator}0x{address:02X} {name:<16} = 0x{:02X} {}",
+                    raw[address as usize],
+                    Fields::decode(&value)
+                )
+            });
+        });
+        result
+    }
+}
+
+impl RegisterSnapshot {
+    /// [`Display`](core::fmt::Display) wrapper printing all registers with decoded fields
+    pub fn dump(&self) -> RegisterDump<'_> {
+        RegisterDump(self)
+    }
+}
+
+/// Log every register of `snapshot` at info level through the `defmt`/`log` macros
+///
+/// Without either feature this is a no-op.
+pub(crate) fn log_registers(snapshot: &RegisterSnapshot) {
+    #[cfg(any(feature = "log", feature = "defmt"))]
+    for_each_register(snapshot, |address, name, value| {
+        info!(
+            "PCF8563: {:#04x} {} = {:#04x} {}",
+            address,
+            name,
+            snapshot.raw()[address as usize],
+            Fields::decode(&value)
+        );
+    });
+    #[cfg(not(any(feature = "log", feature = "defmt")))]
+    let _ = snapshot;
+}
+
+/// Decode every register of `snapshot` with the generated field sets
+fn for_each_register(
+    snapshot: &RegisterSnapshot,
+    callback: impl FnMut(u8, &'static str, FieldSetValue),
+) {
+    let mut ll = Pcf8563LowLevel::new(SnapshotInterface(snapshot.raw()));
+    // Reading from memory cannot fail
+    let Ok(()) = ll.read_all_registers(callback);
+}
+
+/// Value of one decoded field
+#[derive(Clone, Copy)]
+enum Value {
+    Number(u8),
+    Text(&'static str),
+}
+
+/// Fields of one register as `NAME=value` pairs, e.g. `AE_M=disabled minute=37`
+struct Fields([Option<(&'static str, Value)>; 5]);
+
+impl Fields {
+    fn decode(value: &FieldSetValue) -> Self {
+        use Value::{Number, Text};
+
+        let bcd = |ten: u8, unit: u8| Number(ten * 10 + unit);
+        let flag = |set: bool| Text(if set { "set" } else { "clear" });
+        let enabled = |enabled: bool| Text(if enabled { "enabled" } else { "disabled" });
+        // Alarm enable bits are active low
+        let alarm = |ae: bool| enabled(!ae);
+
+        let fields: &[(&'static str, Value)] = match value {
+            FieldSetValue::ControlStatus1(r) => &[
+                ("TEST1", Text(if r.test1() { "ext_clk" } else { "normal" })),
+                ("STOP", Text(if r.stop() { "stopped" } else { "running" })),
+                ("TESTC", enabled(r.testc() == PorOverride::Enabled)),
+            ],
+            FieldSetValue::ControlStatus2(r) => &[
+                ("TI_TP", Text(if r.ti_tp() { "pulse" } else { "level" })),
+                ("AF", flag(r.af())),
+                ("TF", flag(r.tf())),
+                ("AIE", enabled(r.aie())),
+                ("TIE", enabled(r.tie())),
+            ],
+            FieldSetValue::Seconds(r) => &[
+                ("VL", flag(r.vl())),
+                ("second", bcd(r.seconds_ten(), r.seconds_unit())),
+            ],
+            FieldSetValue::Minutes(r) => &[("minute", bcd(r.minutes_ten(), r.minutes_unit()))],
+            FieldSetValue::Hours(r) => &[("hour", bcd(r.hours_ten(), r.hours_unit()))],
+            FieldSetValue::Days(r) => &[("day", bcd(r.days_ten(), r.days_unit()))],
+            FieldSetValue::Weekdays(r) => &[("weekday", Number(r.weekday()))],
+            FieldSetValue::CenturyMonths(r) => &[
+                ("C", Number(r.century() as u8)),
+                ("month", bcd(r.months_ten(), r.months_unit())),
+            ],
+            FieldSetValue::Years(r) => &[("year", bcd(r.years_ten(), r.years_unit()))],
+            FieldSetValue::MinuteAlarm(r) => &[
+                ("AE_M", alarm(r.ae_m())),
+                ("minute", bcd(r.minute_alarm_ten(), r.minute_alarm_unit())),
+            ],
+            FieldSetValue::HourAlarm(r) => &[
+                ("AE_H", alarm(r.ae_h())),
+                ("hour", bcd(r.hour_alarm_ten(), r.hour_alarm_unit())),
+            ],
+            FieldSetValue::DayAlarm(r) => &[
+                ("AE_D", alarm(r.ae_d())),
+                ("day", bcd(r.day_alarm_ten(), r.day_alarm_unit())),
+            ],
+            FieldSetValue::WeekdayAlarm(r) => &[
+                ("AE_W", alarm(r.ae_w())),
+                ("weekday", Number(r.weekday_alarm())),
+            ],
+            FieldSetValue::ClkoutControl(r) => &[
+                ("FE", enabled(r.fe())),
+                (
+                    "FD",
+                    Text(match r.fd() {
+                        ClkoutFrequency::Freq32768Hz => "32768Hz",
+                        ClkoutFrequency::Freq1024Hz => "1024Hz",
+                        ClkoutFrequency::Freq32Hz => "32Hz",
+                        ClkoutFrequency::Freq1Hz => "1Hz",
+                    }),
+                ),
+            ],
+            FieldSetValue::TimerControl(r) => &[
+                ("TE", enabled(r.te())),
+                (
+                    "TD",
+                    Text(match r.td() {
+                        TimerFrequency::Freq4096Hz => "4096Hz",
+                        TimerFrequency::Freq64Hz => "64Hz",
+                        TimerFrequency::Freq1Hz => "1Hz",
+                        TimerFrequency::Freq160Hz => "1/60Hz",
+                    }),
+                ),
+            ],
+            FieldSetValue::Timer(r) => &[("timer", Number(r.timer_value()))],
+        };
+
+        let mut decoded = [None; 5];
+        for (slot, &field) in decoded.iter_mut().zip(fields) {
+            *slot = Some(field);
+        }
+        Self(decoded)
+    }
+}
+
+impl fmt::Display for Fields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (name, value)) in self.0.iter().flatten().enumerate() {
+            let separator = if index > 0 { " " } else { "" };
+            match value {
+                Value::Number(n) => write!(f, "{separator}{name}={n}")?,
+                Value::Text(text) => write!(f, "{separator}{name}={text}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Fields {
+    fn format(&self, f: defmt::Formatter) {
+        for (index, (name, value)) in self.0.iter().flatten().enumerate() {
+            if index > 0 {
+                defmt::write!(f, " ");
+            }
+            match value {
+                Value::Number(n) => defmt::write!(f, "{=str}={=u8}", name, n),
+                Value::Text(text) => defmt::write!(f, "{=str}={=str}", name, text),
+            }
+        }
+    }
+}
+
+/// Read-only register interface backed by a snapshot
+struct SnapshotInterface<'a>(&'a [u8; REGISTER_COUNT]);
+
+impl device_driver::RegisterInterface for SnapshotInterface<'_> {
+    type AddressType = u8;
+    type Error = Infallible;
+
+    fn read_register(
+        &mut self,
+        address: u8,
+        _size_bits: u32,
+        data: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let start = address as usize;
+        data.copy_from_slice(&self.0[start..start + data.len()]);
+        Ok(())
+    }
+
+    fn write_register(
+        &mut self,
+        _address: u8,
+        _size_bits: u32,
+        _data: &[u8],
+    ) -> Result<(), Self::Error> {
+        // Never called: the dump only reads
+        Ok(())
+    }
+}
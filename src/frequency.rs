@@ -0,0 +1,107 @@
+//! `fugit` conversions for the CLKOUT and timer frequency settings
+
+use crate::{ClkoutFrequency, TimerFrequency};
+use fugit::{Duration, HertzU32, Rate};
+use thiserror::Error;
+
+/// The requested frequency is not one of the settings supported by the RTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("Unsupported frequency")]
+pub struct UnsupportedFrequency(pub HertzU32);
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for UnsupportedFrequency {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "UnsupportedFrequency({=u32} Hz)", self.0.raw())
+    }
+}
+
+impl ClkoutFrequency {
+    /// Output frequency
+    pub const fn to_hertz(self) -> HertzU32 {
+        HertzU32::from_raw(match self {
+            ClkoutFrequency::Freq32768Hz => 32_768,
+            ClkoutFrequency::Freq1024Hz => 1024,
+            ClkoutFrequency::Freq32Hz => 32,
+            ClkoutFrequency::Freq1Hz => 1,
+        })
+    }
+
+    /// Output period, in cycles of the 32.768 kHz crystal
+    pub const fn period(self) -> Duration<u32, 1, 32_768> {
+        Duration::<u32, 1, 32_768>::from_ticks(match self {
+            ClkoutFrequency::Freq32768Hz => 1,
+            ClkoutFrequency::Freq1024Hz => 32,
+            ClkoutFrequency::Freq32Hz => 1024,
+            ClkoutFrequency::Freq1Hz => 32_768,
+        })
+    }
+}
+
+impl From<ClkoutFrequency> for HertzU32 {
+    fn from(freq: ClkoutFrequency) -> Self {
+        freq.to_hertz()
+    }
+}
+
+impl TryFrom<HertzU32> for ClkoutFrequency {
+    type Error = UnsupportedFrequency;
+
+    fn try_from(freq: HertzU32) -> Result<Self, Self::Error> {
+        match freq.raw() {
+            32_768 => Ok(ClkoutFrequency::Freq32768Hz),
+            1024 => Ok(ClkoutFrequency::Freq1024Hz),
+            32 => Ok(ClkoutFrequency::Freq32Hz),
+            1 => Ok(ClkoutFrequency::Freq1Hz),
+            _ => Err(UnsupportedFrequency(freq)),
+        }
+    }
+}
+
+impl TimerFrequency {
+    /// Source clock frequency
+    ///
+    /// Returns `None` for [`TimerFrequency::Freq160Hz`], which has no integer
+    /// Hz representation; use [`to_rate`](Self::to_rate) for an exact value.
+    pub const fn to_hertz(self) -> Option<HertzU32> {
+        match self {
+            TimerFrequency::Freq4096Hz => Some(HertzU32::from_raw(4096)),
+            TimerFrequency::Freq64Hz => Some(HertzU32::from_raw(64)),
+            TimerFrequency::Freq1Hz => Some(HertzU32::from_raw(1)),
+            TimerFrequency::Freq160Hz => None,
+        }
+    }
+
+    /// Source clock frequency in units of 1/60 Hz (exact for all settings)
+    pub const fn to_rate(self) -> Rate<u32, 1, 60> {
+        Rate::<u32, 1, 60>::from_raw(match self {
+            TimerFrequency::Freq4096Hz => 4096 * 60,
+            TimerFrequency::Freq64Hz => 64 * 60,
+            TimerFrequency::Freq1Hz => 60,
+            TimerFrequency::Freq160Hz => 1,
+        })
+    }
+
+    /// Period of one timer tick, in units of 1/4096 s (exact for all settings)
+    pub const fn period(self) -> Duration<u32, 1, 4096> {
+        Duration::<u32, 1, 4096>::from_ticks(match self {
+            TimerFrequency::Freq4096Hz => 1,
+            TimerFrequency::Freq64Hz => 64,
+            TimerFrequency::Freq1Hz => 4096,
+            TimerFrequency::Freq160Hz => 4096 * 60,
+        })
+    }
+}
+
+impl TryFrom<HertzU32> for TimerFrequency {
+    type Error = UnsupportedFrequency;
+
+    fn try_from(freq: HertzU32) -> Result<Self, Self::Error> {
+        match freq.raw() {
+            4096 => Ok(TimerFrequency::Freq4096Hz),
+            64 => Ok(TimerFrequency::Freq64Hz),
+            1 => Ok(TimerFrequency::Freq1Hz),
+            _ => Err(UnsupportedFrequency(freq)),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::calendar::{days_in_month, full_year};
+use crate::decode::invalid_datetime_field;
+use crate::{Field, RegisterSnapshot, TestModeStatus};
+
+/// Recommended action from a [`ClockHealth`] assessment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HealthAction {
+    /// Clock is running and holds a plausible time; nothing to do
+    Valid,
+    /// Time was lost (voltage-low flag set); set the time
+    NeedsTimeSet,
+    /// Oscillator is stopped (STOP bit set, or seconds did not advance);
+    /// start the clock and set the time
+    OscillatorStopped,
+    /// Registers hold invalid values or a test mode is active; run `init()`
+    /// and set the time
+    Corrupted,
+}
+
+/// Boot-time clock health report, returned by `check_health()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ClockHealth {
+    /// Voltage-low flag: clock integrity is not guaranteed
+    pub voltage_low: bool,
+    /// STOP bit: the RTC divider chain is held in reset
+    pub stopped: bool,
+    /// State of TEST1 and TESTC
+    pub test_modes: TestModeStatus,
+    /// First time register holding invalid BCD or an out-of-range value
+    pub invalid_field: Option<Field>,
+    /// Day of month exists in the stored month and year
+    pub calendar_valid: bool,
+    /// Whether the seconds register advanced during the check, if it was run
+    pub seconds_advanced: Option<bool>,
+    /// Recommended action
+    pub action: HealthAction,
+}
+
+impl ClockHealth {
+    /// Assess a register snapshot, optionally with the result of a
+    /// "seconds advanced" check
+    ///
+    /// The POR override (TESTC) is reported but does not affect the action,
+    /// as it is the reset default and is cleared by `init()`.
+    pub(crate) fn assess(snapshot: &RegisterSnapshot, seconds_advanced: Option<bool>) -> Self {
+        let control = snapshot.control_status_1();
+        let registers = snapshot.datetime_registers();
+        let invalid_field = invalid_datetime_field(&registers).map(|(field, _)| field);
+        let calendar_valid = invalid_field.is_none() && {
+            let dt = snapshot.datetime();
+            let year = full_year(dt.year, snapshot.century_months().century());
+            dt.day <= days_in_month(year, dt.month)
+        };
+
+        let voltage_low = snapshot.seconds().vl();
+        let stopped = control.stop();
+        let test_modes = TestModeStatus {
+            ext_clk_test_mode: control.test1(),
+            por_override: control.testc(),
+        };
+
+        let action = if test_modes.ext_clk_test_mode {
+            HealthAction::Corrupted
+        } else if stopped {
+            HealthAction::OscillatorStopped
+        } else if voltage_low {
+            HealthAction::NeedsTimeSet
+        } else if !calendar_valid {
+            HealthAction::Corrupted
+        } else if seconds_advanced == Some(false) {
+            HealthAction::OscillatorStopped
+        } else {
+            HealthAction::Valid
+        };
+
+        Self {
+            voltage_low,
+            stopped,
+            test_modes,
+            invalid_field,
+            calendar_valid,
+            seconds_advanced,
+            action,
+        }
+    }
+
+    /// Returns `true` if no action is needed
+    pub fn is_valid(&self) -> bool {
+        self.action == HealthAction::Valid
+    }
+}
@@ -17,6 +17,8 @@
 //! *   **Optional `rtcc` Traits (blocking):** Enable the `rtcc` feature to implement
 //!     [`rtcc::DateTimeAccess`](https://docs.rs/rtcc/latest/rtcc/trait.DateTimeAccess.html)
 //!     and [`rtcc::Rtcc`](https://docs.rs/rtcc/latest/rtcc/trait.Rtcc.html) on the blocking driver.
+//! *   **Optional `fugit` Conversions:** Enable the `fugit` feature to convert CLKOUT and timer
+//!     frequencies to and from [`fugit::HertzU32`](https://docs.rs/fugit) and period durations.
 //! *   **`defmt` and `log` Integration:** Optional support for logging and debugging.
 //!
 //! ## Getting Started
@@ -56,6 +58,57 @@
 #[macro_use]
 pub(crate) mod fmt;
 
+mod build_time;
+mod calendar;
+mod calibration;
+mod decode;
+mod drift;
+mod dump;
+mod health;
+mod nmea;
+mod radio;
+mod read_mode;
+mod retry;
+mod snapshot;
+mod sntp;
+mod stop;
+mod subsec;
+mod sync;
+mod temperature;
+mod test_modes;
+mod textdate;
+mod variant;
+#[doc(hidden)]
+pub use build_time::__parse_build_time;
+pub use build_time::BUILD_TIME_ENV;
+#[cfg(feature = "std")]
+pub use build_time::emit_build_time;
+pub use calibration::{CalibrationError, CaptureError, McuCalibration, Monotonic};
+pub use drift::{DriftEstimate, DriftEstimator, DriftParams, DriftSample};
+pub use dump::RegisterDump;
+pub use health::{ClockHealth, HealthAction};
+pub use nmea::{NmeaError, parse_nmea};
+pub use radio::{RadioClockDecoder, RadioError, RadioProtocol, RadioTime};
+pub use read_mode::ReadMode;
+pub use retry::{RetryInterface, RetryPolicy};
+pub use snapshot::RegisterSnapshot;
+pub use sntp::{
+    LeapIndicator, NTP_PACKET_LEN, NtpTimestamp, SntpError, SntpMeasurement, SntpPacket,
+    SntpRequest, build_sntp_request, process_sntp_response,
+};
+pub use stop::{EdgeError, STOP_RELEASE_DELAY_US};
+pub use subsec::{SUBSEC_RESYNC_SECS, SubsecDateTime};
+pub use sync::{SyncError, SyncOutcome};
+pub use temperature::{TemperatureCompensation, TemperatureModel, TemperatureSample};
+pub use test_modes::TestModeStatus;
+pub use textdate::{DateParseError, parse_cclk, parse_http_date, parse_rfc2822};
+pub use variant::Variant;
+
+#[cfg(feature = "fugit")]
+mod frequency;
+#[cfg(feature = "fugit")]
+pub use frequency::UnsupportedFrequency;
+
 use thiserror::Error;
 
 device_driver::create_device!(device_name: Pcf8563LowLevel, manifest: "device.yaml");
@@ -63,13 +116,138 @@ device_driver::create_device!(device_name: Pcf8563LowLevel, manifest: "device.ya
 /// PCF8563/BM8563 I2C address (7-bit)
 pub const PCF8563_I2C_ADDR: u8 = 0x51;
 
+/// Number of registers in the PCF8563 register file (0x00-0x0F)
+pub const REGISTER_COUNT: usize = 16;
+
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum RtcError<I2cErr> {
+    /// I2C error not covered by a more specific variant
     #[error("I2C error")]
     I2c(I2cErr),
-    #[error("Invalid input data")]
-    InvalidInputData,
+    /// The device did not acknowledge its address
+    #[error("Device not present (address NACK)")]
+    DeviceNotPresent(I2cErr),
+    /// Misplaced START/STOP condition or other bus-level fault
+    #[error("I2C bus error")]
+    BusError(I2cErr),
+    /// Another controller won arbitration on a multi-controller bus
+    #[error("I2C arbitration lost")]
+    ArbitrationLost(I2cErr),
+    /// A value is out of range for the given field
+    #[error("Invalid value {value} for {field:?}")]
+    InvalidField { field: Field, value: u32 },
+    /// A register read back invalid BCD or an out-of-range value
+    #[error("Invalid register value {raw:#04x} for {field:?}")]
+    InvalidRegisterValue { field: Field, raw: u8 },
+    /// A register transfer would run past the last register (0x0F)
+    #[error("Register access at {address:#04x} of {len} bytes out of range")]
+    RegisterOutOfRange { address: u8, len: usize },
+    /// Repeated time register reads never agreed (see [`ReadMode`])
+    #[error("Inconsistent time register reads")]
+    InconsistentRead,
+    /// The seconds register did not advance within 1.1 s
+    #[error("Clock not running")]
+    ClockNotRunning,
+    /// A [`Monotonic`] reported a tick rate of 0 Hz
+    #[error("Invalid monotonic tick rate")]
+    InvalidTickRate,
+}
+
+/// Field identifier used in validation errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Field {
+    Seconds,
+    Minutes,
+    Hours,
+    Day,
+    Weekday,
+    Month,
+    Year,
+    MinuteAlarm,
+    HourAlarm,
+    DayAlarm,
+    WeekdayAlarm,
+    ClkoutFrequency,
+}
+
+impl<E: embedded_hal::i2c::Error> RtcError<E> {
+    /// Classify an I2C error by its [`ErrorKind`](embedded_hal::i2c::ErrorKind)
+    pub fn from_i2c(error: E) -> Self {
+        use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+        match error.kind() {
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => {
+                RtcError::DeviceNotPresent(error)
+            }
+            ErrorKind::Bus => RtcError::BusError(error),
+            ErrorKind::ArbitrationLoss => RtcError::ArbitrationLost(error),
+            _ => RtcError::I2c(error),
+        }
+    }
+
+    /// Whether the error is a transient bus condition worth retrying
+    ///
+    /// NACKs, arbitration loss, bus errors and overruns are retryable;
+    /// invalid arguments and unclassified bus errors are not.
+    pub fn is_retryable(&self) -> bool {
+        use embedded_hal::i2c::ErrorKind;
+
+        match self {
+            RtcError::DeviceNotPresent(_)
+            | RtcError::BusError(_)
+            | RtcError::ArbitrationLost(_) => true,
+            RtcError::I2c(e) => matches!(
+                e.kind(),
+                ErrorKind::NoAcknowledge(_)
+                    | ErrorKind::ArbitrationLoss
+                    | ErrorKind::Bus
+                    | ErrorKind::Overrun
+            ),
+            RtcError::InvalidField { .. }
+            | RtcError::InvalidRegisterValue { .. }
+            | RtcError::RegisterOutOfRange { .. }
+            | RtcError::InconsistentRead
+            | RtcError::ClockNotRunning
+            | RtcError::InvalidTickRate => false,
+        }
+    }
+}
+
+impl<E> RtcError<E> {
+    /// The underlying I2C error, if any
+    pub fn i2c_error(&self) -> Option<&E> {
+        match self {
+            RtcError::I2c(e)
+            | RtcError::DeviceNotPresent(e)
+            | RtcError::BusError(e)
+            | RtcError::ArbitrationLost(e) => Some(e),
+            RtcError::InvalidField { .. }
+            | RtcError::InvalidRegisterValue { .. }
+            | RtcError::RegisterOutOfRange { .. }
+            | RtcError::InconsistentRead
+            | RtcError::ClockNotRunning
+            | RtcError::InvalidTickRate => None,
+        }
+    }
+}
+
+/// Check that `value` lies in `range`, reporting `field` otherwise
+#[inline]
+pub(crate) fn check_field<E>(
+    field: Field,
+    value: u8,
+    range: core::ops::RangeInclusive<u8>,
+) -> Result<(), RtcError<E>> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(RtcError::InvalidField {
+            field,
+            value: value as u32,
+        })
+    }
 }
 
 /// Date and time structure
@@ -92,6 +270,38 @@ pub struct DateTime {
     pub seconds: u8,
 }
 
+impl DateTime {
+    /// Seconds since the Unix epoch, treating `year` as 2000-2099
+    ///
+    /// The weekday is ignored. Fields are not validated; use values read
+    /// with `get_datetime()` or checked by `set_datetime()`.
+    pub const fn to_unix_timestamp(&self) -> i64 {
+        let days = calendar::days_from_civil(2000 + self.year as u16, self.month, self.day);
+        days * 86_400 + self.hours as i64 * 3600 + self.minutes as i64 * 60 + self.seconds as i64
+    }
+
+    /// Date and time for a Unix timestamp, or `None` outside 2000-2099
+    ///
+    /// The weekday is derived from the date (0 = Sunday).
+    pub const fn from_unix_timestamp(timestamp: i64) -> Option<Self> {
+        let days = timestamp.div_euclid(86_400);
+        let secs = timestamp.rem_euclid(86_400);
+        let (year, month, day) = calendar::civil_from_days(days);
+        if year < 2000 || year > 2099 {
+            return None;
+        }
+        Some(Self {
+            year: (year - 2000) as u8,
+            month,
+            day,
+            weekday: calendar::weekday(year as u16, month, day),
+            hours: (secs / 3600) as u8,
+            minutes: (secs / 60 % 60) as u8,
+            seconds: (secs % 60) as u8,
+        })
+    }
+}
+
 /// Time-only structure (for clock applications without calendar)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -120,11 +330,28 @@ pub struct Alarm {
 
 pub struct Pcf8563Interface<I2CBus> {
     i2c_bus: I2CBus,
+    address: u8,
 }
 
 impl<I2CBus> Pcf8563Interface<I2CBus> {
     pub fn new(i2c_bus: I2CBus) -> Self {
-        Self { i2c_bus }
+        Self::new_with_address(i2c_bus, PCF8563_I2C_ADDR)
+    }
+
+    /// Create an interface for a device at a non-default 7-bit I2C address
+    /// (e.g. behind an address translator or for a pin-compatible clone)
+    pub fn new_with_address(i2c_bus: I2CBus, address: u8) -> Self {
+        Self { i2c_bus, address }
+    }
+
+    /// 7-bit I2C address used for all transfers
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Consume the interface and return the I2C bus
+    pub fn release(self) -> I2CBus {
+        self.i2c_bus
     }
 }
 
@@ -132,31 +359,36 @@ impl<I2CBus> Pcf8563Interface<I2CBus> {
 mod asynchronous {
     use bisync::asynchronous::*;
     use device_driver::AsyncRegisterInterface as RegisterInterface;
+    use embedded_hal_async::delay::DelayNs;
+    use embedded_hal_async::digital::Wait as EdgeInput;
     use embedded_hal_async::i2c::I2c;
+    mod capture;
+    mod compensation;
     mod driver;
+    pub use capture::*;
+    pub use compensation::*;
     pub use driver::*;
 }
-pub use asynchronous::Pcf8563 as Pcf8563Async;
+pub use asynchronous::{
+    ClkoutCapture as ClkoutCaptureAsync, DriftCompensated as DriftCompensatedAsync,
+    EdgeCounter as EdgeCounterAsync, Pcf8563 as Pcf8563Async,
+};
 
 #[path = "."]
 mod blocking {
     use bisync::synchronous::*;
     use device_driver::RegisterInterface;
+    use embedded_hal::delay::DelayNs;
+    use embedded_hal::digital::InputPin as EdgeInput;
     use embedded_hal::i2c::I2c;
     #[allow(clippy::duplicate_mod)]
+    mod capture;
+    #[allow(clippy::duplicate_mod)]
+    mod compensation;
+    #[allow(clippy::duplicate_mod)]
     mod driver;
+    pub use capture::*;
+    pub use compensation::*;
     pub use driver::*;
 }
-pub use blocking::Pcf8563;
-
-/// Convert BCD to decimal
-#[inline]
-pub(crate) fn bcd_to_dec(bcd: u8) -> u8 {
-    (bcd & 0x0F) + ((bcd >> 4) * 10)
-}
-
-/// Convert decimal to BCD
-#[inline]
-pub(crate) fn dec_to_bcd(dec: u8) -> u8 {
-    ((dec / 10) << 4) | (dec % 10)
-}
+pub use blocking::{ClkoutCapture, DriftCompensated, EdgeCounter, Pcf8563};
@@ -0,0 +1,129 @@
+use crate::{DateTime, calendar};
+use thiserror::Error;
+
+/// Reason an NMEA sentence did not yield a date and time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NmeaError {
+    #[error("Malformed NMEA sentence")]
+    Malformed,
+    #[error("NMEA checksum mismatch")]
+    Checksum,
+    /// A valid sentence other than RMC or ZDA (e.g. GGA, GSV)
+    #[error("Unsupported NMEA sentence")]
+    Unsupported,
+    /// The receiver reports no valid fix, or the time fields are empty
+    #[error("No valid GPS fix")]
+    NoFix,
+    /// Date or time outside 2000-2099 or otherwise invalid
+    #[error("Date/time out of range")]
+    OutOfRange,
+}
+
+/// Parse the UTC date and time from a `$xxRMC` or `$xxZDA` sentence
+///
+/// Accepts any talker ID (GP, GN, GL, GA, ...). The checksum is required
+/// and verified. RMC sentences must report status `A` (and a mode other than
+/// `N` when present). Fractional seconds are truncated; a leap second (60)
+/// is rejected with [`NmeaError::OutOfRange`].
+pub fn parse_nmea(sentence: &str) -> Result<DateTime, NmeaError> {
+    if !sentence.is_ascii() {
+        return Err(NmeaError::Malformed);
+    }
+    let body = sentence
+        .trim_end()
+        .strip_prefix('$')
+        .ok_or(NmeaError::Malformed)?;
+    let (body, checksum) = body.split_once('*').ok_or(NmeaError::Malformed)?;
+    if checksum.len() != 2 {
+        return Err(NmeaError::Malformed);
+    }
+    let expected = u8::from_str_radix(checksum, 16).map_err(|_| NmeaError::Malformed)?;
+    if body.bytes().fold(0, |acc, b| acc ^ b) != expected {
+        return Err(NmeaError::Checksum);
+    }
+
+    let mut fields = body.split(',');
+    let address = fields.next().ok_or(NmeaError::Malformed)?;
+    if address.len() != 5 {
+        return Err(NmeaError::Malformed);
+    }
+    match address.get(2..) {
+        Some("RMC") => parse_rmc(fields),
+        Some("ZDA") => parse_zda(fields),
+        _ => Err(NmeaError::Unsupported),
+    }
+}
+
+/// `hhmmss.ss,A,llll.ll,a,yyyyy.yy,a,x.x,x.x,ddmmyy,x.x,a[,m]`
+fn parse_rmc<'a>(mut fields: impl Iterator<Item = &'a str>) -> Result<DateTime, NmeaError> {
+    let mut field = || fields.next().ok_or(NmeaError::Malformed);
+    let time = field()?;
+    let status = field()?;
+    for _ in 0..6 {
+        field()?; // position, speed and course
+    }
+    let date = field()?;
+    let _ = (field(), field()); // magnetic variation
+    let mode = field().ok();
+
+    if status != "A" || mode == Some("N") || time.is_empty() || date.is_empty() {
+        return Err(NmeaError::NoFix);
+    }
+    let (hours, minutes, seconds) = parse_time(time)?;
+    if date.len() != 6 {
+        return Err(NmeaError::Malformed);
+    }
+    let day = digits(&date[0..2])? as u8;
+    let month = digits(&date[2..4])? as u8;
+    let year = 2000 + digits(&date[4..6])? as u16;
+    calendar::datetime(year, month, day, hours, minutes, seconds).ok_or(NmeaError::OutOfRange)
+}
+
+/// `hhmmss.ss,dd,mm,yyyy,zh,zm`
+fn parse_zda<'a>(mut fields: impl Iterator<Item = &'a str>) -> Result<DateTime, NmeaError> {
+    let mut field = || fields.next().ok_or(NmeaError::Malformed);
+    let time = field()?;
+    let day = field()?;
+    let month = field()?;
+    let year = field()?;
+
+    if time.is_empty() || day.is_empty() || month.is_empty() || year.is_empty() {
+        return Err(NmeaError::NoFix);
+    }
+    let (hours, minutes, seconds) = parse_time(time)?;
+    if day.len() != 2 || month.len() != 2 || year.len() != 4 {
+        return Err(NmeaError::Malformed);
+    }
+    calendar::datetime(
+        digits(year)? as u16,
+        digits(month)? as u8,
+        digits(day)? as u8,
+        hours,
+        minutes,
+        seconds,
+    )
+    .ok_or(NmeaError::OutOfRange)
+}
+
+/// `hhmmss` with optional fractional seconds
+fn parse_time(field: &str) -> Result<(u8, u8, u8), NmeaError> {
+    let (whole, fraction) = field.split_once('.').unwrap_or((field, "0"));
+    if whole.len() != 6 || fraction.is_empty() {
+        return Err(NmeaError::Malformed);
+    }
+    digits(fraction)?;
+    Ok((
+        digits(&whole[0..2])? as u8,
+        digits(&whole[2..4])? as u8,
+        digits(&whole[4..6])? as u8,
+    ))
+}
+
+/// Value of an all-ASCII-digit field
+fn digits(field: &str) -> Result<u32, NmeaError> {
+    if field.is_empty() || field.len() > 9 || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(NmeaError::Malformed);
+    }
+    Ok(field.bytes().fold(0, |acc, b| acc * 10 + (b - b'0') as u32))
+}
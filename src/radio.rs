@@ -0,0 +1,355 @@
+use crate::{DateTime, calendar};
+use thiserror::Error;
+
+/// Radio time signal transmitted by the receiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RadioProtocol {
+    /// DCF77, Mainflingen (Germany): CET/CEST
+    Dcf77,
+    /// MSF, Anthorn (UK): GMT/BST
+    Msf,
+}
+
+/// Reason a minute frame was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RadioError {
+    /// A pulse or gap did not match the protocol timing (noise)
+    #[error("Radio pulse timing error")]
+    Timing,
+    /// The frame had the wrong number of seconds or fixed bits
+    #[error("Invalid radio time frame")]
+    InvalidFrame,
+    #[error("Radio time parity error")]
+    Parity,
+    /// Fields decode to an impossible date or time
+    #[error("Radio time out of range")]
+    OutOfRange,
+}
+
+/// Decoded minute frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RadioTime {
+    /// UTC time at the minute mark
+    pub utc: DateTime,
+    /// Transmitted local time (CET/CEST or GMT/BST) at the minute mark
+    pub local: DateTime,
+    /// Summer time (CEST/BST) is in effect
+    pub summer_time: bool,
+    /// A summer time change follows at the end of this hour
+    pub summer_time_change: bool,
+    /// A leap second is inserted at the end of this hour (DCF77 only)
+    pub leap_second: bool,
+    /// Tick count at the minute mark (start of second 0)
+    pub minute_start_ticks: u64,
+}
+
+/// Decodes DCF77 or MSF minute frames from receiver edge timestamps
+///
+/// Feed every edge of the receiver output with [`edge`](Self::edge), passing
+/// `active = true` when the carrier is reduced (DCF77) or off (MSF) and
+/// `false` when it returns; invert the level if your module's output is the
+/// other way round. A frame is returned at the minute mark that ends it, so
+/// the time is known within the edge-timestamp latency.
+#[derive(Debug, Clone)]
+pub struct RadioClockDecoder {
+    protocol: RadioProtocol,
+    tick_hz: u32,
+    pulse_start: Option<u64>,
+    second_start: Option<u64>,
+    /// Current second within the frame, `None` until a minute mark is seen
+    second: Option<u8>,
+    a_bits: u64,
+    b_bits: u64,
+}
+
+impl RadioClockDecoder {
+    pub const fn new(protocol: RadioProtocol, tick_hz: u32) -> Self {
+        Self {
+            protocol,
+            tick_hz,
+            pulse_start: None,
+            second_start: None,
+            second: None,
+            a_bits: 0,
+            b_bits: 0,
+        }
+    }
+
+    /// Discard the frame in progress and wait for the next minute mark
+    pub fn reset(&mut self) {
+        self.second = None;
+        self.a_bits = 0;
+        self.b_bits = 0;
+    }
+
+    /// Process an edge at tick count `ticks`
+    ///
+    /// Returns `Ok(Some(_))` at a minute mark completing a valid frame and
+    /// `Err(_)` when a frame in progress is rejected; the decoder then
+    /// resynchronizes at the next minute mark.
+    pub fn edge(&mut self, ticks: u64, active: bool) -> Result<Option<RadioTime>, RadioError> {
+        let result = match (self.protocol, active) {
+            (RadioProtocol::Dcf77, true) => self.dcf77_pulse_start(ticks),
+            (RadioProtocol::Dcf77, false) => self.dcf77_pulse_end(ticks),
+            (RadioProtocol::Msf, true) => self.msf_pulse_start(ticks),
+            (RadioProtocol::Msf, false) => self.msf_pulse_end(ticks),
+        };
+        if result.is_err() {
+            self.reset();
+        }
+        result
+    }
+
+    fn ms(&self, from: u64, to: u64) -> u64 {
+        to.wrapping_sub(from) * 1000 / self.tick_hz as u64
+    }
+
+    /// Report a timing error only while a frame is in progress
+    fn timing_error(&self) -> Result<Option<RadioTime>, RadioError> {
+        match self.second {
+            Some(_) => Err(RadioError::Timing),
+            None => Ok(None),
+        }
+    }
+
+    // DCF77: every second except 59 starts with a 100 ms (0) or 200 ms (1)
+    // carrier reduction; the missing pulse marks the minute.
+
+    fn dcf77_pulse_start(&mut self, ticks: u64) -> Result<Option<RadioTime>, RadioError> {
+        let previous = self.second_start.replace(ticks);
+        self.pulse_start = Some(ticks);
+        let Some(previous) = previous else {
+            return Ok(None);
+        };
+
+        match self.ms(previous, ticks) {
+            800..=1200 => match self.second {
+                Some(second) if second < 59 => {
+                    self.second = Some(second + 1);
+                    Ok(None)
+                }
+                Some(_) => Err(RadioError::InvalidFrame),
+                None => Ok(None),
+            },
+            1700..=2200 => {
+                let result = match self.second {
+                    Some(last) => self.decode_dcf77(last, ticks).map(Some),
+                    None => Ok(None),
+                };
+                self.second = Some(0);
+                self.a_bits = 0;
+                result
+            }
+            _ => self.timing_error(),
+        }
+    }
+
+    fn dcf77_pulse_end(&mut self, ticks: u64) -> Result<Option<RadioTime>, RadioError> {
+        let Some(start) = self.pulse_start.take() else {
+            return Ok(None);
+        };
+        let bit = match self.ms(start, ticks) {
+            40..=139 => 0,
+            140..=260 => 1,
+            _ => return self.timing_error(),
+        };
+        if let Some(second) = self.second {
+            self.a_bits |= bit << second;
+        }
+        Ok(None)
+    }
+
+    fn decode_dcf77(&self, last: u8, ticks: u64) -> Result<RadioTime, RadioError> {
+        let bits = self.a_bits;
+        let bit = |n: u32| (bits >> n) & 1 == 1;
+        let leap_second = bit(19);
+        let leap_frame = last == 59 && leap_second && !bit(59);
+        if (last != 58 && !leap_frame) || bit(0) || !bit(20) || bit(17) == bit(18) {
+            return Err(RadioError::InvalidFrame);
+        }
+        if !even_parity(bits, 21..=28) || !even_parity(bits, 29..=35) || !even_parity(bits, 36..=58)
+        {
+            return Err(RadioError::Parity);
+        }
+
+        let bcd = |from: u32, len: u32| -> Result<u8, RadioError> {
+            let raw = ((bits >> from) & ((1 << len) - 1)) as u8;
+            if raw & 0x0F > 9 {
+                return Err(RadioError::OutOfRange);
+            }
+            Ok((raw >> 4) * 10 + (raw & 0x0F))
+        };
+        let minutes = bcd(21, 7)?;
+        let hours = bcd(29, 6)?;
+        let day = bcd(36, 6)?;
+        let weekday = bcd(42, 3)?; // 1 = Monday .. 7 = Sunday
+        let month = bcd(45, 5)?;
+        let year = 2000 + bcd(50, 8)? as u16;
+
+        let summer_time = bit(17);
+        self.finish(
+            (year, month, day, hours, minutes),
+            weekday % 7,
+            if summer_time { 7200 } else { 3600 },
+            summer_time,
+            bit(16),
+            leap_second,
+            ticks,
+        )
+    }
+
+    // MSF: every second starts with the carrier off for 100 ms, followed by
+    // bit A (100-200 ms) and bit B (200-300 ms); second 0 is a 500 ms
+    // minute marker.
+
+    fn msf_pulse_start(&mut self, ticks: u64) -> Result<Option<RadioTime>, RadioError> {
+        if let Some(start) = self.second_start {
+            let offset = self.ms(start, ticks);
+            if offset < 800 {
+                // Second pulse of an A = 0, B = 1 second
+                self.pulse_start = Some(ticks);
+                return match (offset, self.second) {
+                    (150..=250, Some(second)) => {
+                        self.b_bits |= 1 << second;
+                        Ok(None)
+                    }
+                    (150..=250, None) => Ok(None),
+                    _ => self.timing_error(),
+                };
+            }
+            if offset > 1200 {
+                self.second_start = Some(ticks);
+                self.pulse_start = Some(ticks);
+                return self.timing_error();
+            }
+        }
+
+        self.second_start = Some(ticks);
+        self.pulse_start = Some(ticks);
+        // A frame with a leap second ends in second 60
+        match self.second {
+            Some(second) if second < 61 => {
+                self.second = Some(second + 1);
+                Ok(None)
+            }
+            Some(_) => Err(RadioError::InvalidFrame),
+            None => Ok(None),
+        }
+    }
+
+    fn msf_pulse_end(&mut self, ticks: u64) -> Result<Option<RadioTime>, RadioError> {
+        let (Some(start), Some(second_start)) = (self.pulse_start.take(), self.second_start) else {
+            return Ok(None);
+        };
+        if start != second_start {
+            // End of the B pulse
+            return Ok(None);
+        }
+
+        let (a, b) = match self.ms(start, ticks) {
+            40..=149 => (0, 0),
+            150..=249 => (1, 0),
+            250..=399 => (1, 1),
+            400..=650 => {
+                let result = match self.second {
+                    Some(current) if current > 0 => {
+                        self.decode_msf(current - 1, second_start).map(Some)
+                    }
+                    _ => Ok(None),
+                };
+                self.second = Some(0);
+                self.a_bits = 0;
+                self.b_bits = 0;
+                return result;
+            }
+            _ => return self.timing_error(),
+        };
+        if let Some(second) = self.second {
+            self.a_bits |= a << second;
+            self.b_bits |= b << second;
+        }
+        Ok(None)
+    }
+
+    fn decode_msf(&self, last: u8, ticks: u64) -> Result<RadioTime, RadioError> {
+        if !(58..=60).contains(&last) {
+            return Err(RadioError::InvalidFrame);
+        }
+        // Bits 17-59 are counted back from the minute marker, so a leap
+        // second shifts them
+        let shift = last as i32 - 59;
+        let pos = |n: u32| (n as i32 + shift) as u32;
+        let a = |n: u32| (self.a_bits >> pos(n)) & 1 == 1;
+        let b = |n: u32| (self.b_bits >> pos(n)) & 1 == 1;
+
+        let marker = [false, true, true, true, true, true, true, false];
+        if (52..=59).zip(marker).any(|(n, expected)| a(n) != expected) {
+            return Err(RadioError::InvalidFrame);
+        }
+        let odd = |range: core::ops::RangeInclusive<u32>, parity: u32| {
+            (range.filter(|&n| a(n)).count() + b(parity) as usize) % 2 == 1
+        };
+        if !odd(17..=24, 54) || !odd(25..=35, 55) || !odd(36..=38, 56) || !odd(39..=51, 57) {
+            return Err(RadioError::Parity);
+        }
+
+        // Fields are transmitted MSB first
+        let bcd = |from: u32, to: u32| -> Result<u8, RadioError> {
+            let raw = (from..=to).fold(0u8, |acc, n| (acc << 1) | a(n) as u8);
+            if raw & 0x0F > 9 {
+                return Err(RadioError::OutOfRange);
+            }
+            Ok((raw >> 4) * 10 + (raw & 0x0F))
+        };
+        let year = 2000 + bcd(17, 24)? as u16;
+        let month = bcd(25, 29)?;
+        let day = bcd(30, 35)?;
+        let weekday = bcd(36, 38)?; // 0 = Sunday
+        let hours = bcd(39, 44)?;
+        let minutes = bcd(45, 51)?;
+
+        let summer_time = b(58);
+        self.finish(
+            (year, month, day, hours, minutes),
+            weekday,
+            if summer_time { 3600 } else { 0 },
+            summer_time,
+            b(53),
+            false,
+            ticks,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn finish(
+        &self,
+        (year, month, day, hours, minutes): (u16, u8, u8, u8, u8),
+        weekday: u8,
+        utc_offset_secs: i64,
+        summer_time: bool,
+        summer_time_change: bool,
+        leap_second: bool,
+        ticks: u64,
+    ) -> Result<RadioTime, RadioError> {
+        let local = calendar::datetime(year, month, day, hours, minutes, 0)
+            .filter(|local| local.weekday == weekday)
+            .ok_or(RadioError::OutOfRange)?;
+        let utc = DateTime::from_unix_timestamp(local.to_unix_timestamp() - utc_offset_secs)
+            .ok_or(RadioError::OutOfRange)?;
+        Ok(RadioTime {
+            utc,
+            local,
+            summer_time,
+            summer_time_change,
+            leap_second,
+            minute_start_ticks: ticks,
+        })
+    }
+}
+
+fn even_parity(bits: u64, range: core::ops::RangeInclusive<u32>) -> bool {
+    range.filter(|&n| (bits >> n) & 1 == 1).count() % 2 == 0
+}
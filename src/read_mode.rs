@@ -0,0 +1,23 @@
+/// How `get_datetime()` guards against a carry during the time register read
+///
+/// The PCF8563 freezes the time registers for the duration of a burst read,
+/// so a single burst is consistent as long as it is not split. On buses where
+/// the transfer may be interrupted (clock stretching, muxes, shared buses) a
+/// carry can land between bytes, e.g. 12:59:59 read as 12:00:59.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadMode {
+    /// A single 7-byte burst read
+    #[default]
+    Burst,
+    /// Read the time registers twice and retry until both reads agree
+    DoubleRead,
+    /// Read the time registers, then re-read the seconds and retry if they changed
+    SecondsCheck,
+}
+
+impl ReadMode {
+    /// Maximum number of read attempts before giving up with
+    /// [`RtcError::InconsistentRead`](crate::RtcError::InconsistentRead)
+    pub const MAX_ATTEMPTS: u8 = 3;
+}
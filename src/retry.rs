@@ -0,0 +1,66 @@
+/// Retry policy for [`RetryInterface`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RetryPolicy {
+    /// Maximum number of attempts per transfer, including the first one
+    pub attempts: u8,
+    /// Delay before the first retry in microseconds
+    pub backoff_us: u32,
+    /// Factor applied to the delay after each retry (1 for a constant delay)
+    pub backoff_multiplier: u32,
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts with 100 µs and 200 µs pauses in between
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff_us: 100,
+            backoff_multiplier: 2,
+        }
+    }
+}
+
+/// Register interface wrapper that retries transient I2C errors
+///
+/// Each register transfer (including multi-register bursts such as the
+/// 7-byte date/time read) is retried as a whole, so a retried burst is still
+/// consistent. Only errors for which [`RtcError::is_retryable`](crate::RtcError::is_retryable) is `true` are
+/// retried.
+pub struct RetryInterface<Interface, Delay> {
+    pub(crate) interface: Interface,
+    pub(crate) delay: Delay,
+    pub(crate) policy: RetryPolicy,
+    pub(crate) retries: u32,
+}
+
+impl<Interface, Delay> RetryInterface<Interface, Delay> {
+    pub fn new(interface: Interface, delay: Delay, policy: RetryPolicy) -> Self {
+        Self {
+            interface,
+            delay,
+            policy,
+            retries: 0,
+        }
+    }
+
+    /// Total number of retries performed since creation or the last reset
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Reset the retry counter
+    pub fn reset_retries(&mut self) {
+        self.retries = 0;
+    }
+
+    /// Active retry policy
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Consume the wrapper and return the inner interface and delay
+    pub fn release(self) -> (Interface, Delay) {
+        (self.interface, self.delay)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::decode::{decode_alarm, decode_datetime, invalid_datetime_field};
+use crate::field_sets::{
+    CenturyMonths, ClkoutControl, ControlStatus1, ControlStatus2, Seconds, Timer, TimerControl,
+};
+use crate::{Alarm, DateTime, REGISTER_COUNT};
+
+/// Raw copy of the complete register file (0x00-0x0F) with decoded views
+///
+/// Captured in a single burst read with `read_snapshot()` and written back
+/// with `restore_snapshot()`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct RegisterSnapshot {
+    raw: [u8; REGISTER_COUNT],
+}
+
+impl RegisterSnapshot {
+    /// Create a snapshot from raw register values, indexed by register address
+    pub const fn from_raw(raw: [u8; REGISTER_COUNT]) -> Self {
+        Self { raw }
+    }
+
+    /// Raw register values, indexed by register address
+    pub const fn raw(&self) -> &[u8; REGISTER_COUNT] {
+        &self.raw
+    }
+
+    /// Control_status_1 (0x00)
+    pub fn control_status_1(&self) -> ControlStatus1 {
+        [self.raw[0x00]].into()
+    }
+
+    /// Control_status_2 (0x01)
+    pub fn control_status_2(&self) -> ControlStatus2 {
+        [self.raw[0x01]].into()
+    }
+
+    /// VL_seconds (0x02), including the voltage-low flag
+    pub fn seconds(&self) -> Seconds {
+        [self.raw[0x02]].into()
+    }
+
+    /// Century_months (0x07), including the century flag
+    pub fn century_months(&self) -> CenturyMonths {
+        [self.raw[0x07]].into()
+    }
+
+    /// CLKOUT_control (0x0D)
+    pub fn clkout_control(&self) -> ClkoutControl {
+        [self.raw[0x0D]].into()
+    }
+
+    /// Timer_control (0x0E)
+    pub fn timer_control(&self) -> TimerControl {
+        [self.raw[0x0E]].into()
+    }
+
+    /// Timer countdown value (0x0F)
+    pub fn timer(&self) -> Timer {
+        [self.raw[0x0F]].into()
+    }
+
+    /// Decoded date and time (0x02-0x08)
+    pub fn datetime(&self) -> DateTime {
+        decode_datetime(&self.datetime_registers())
+    }
+
+    /// Raw time registers (0x02-0x08)
+    pub(crate) fn datetime_registers(&self) -> [u8; 7] {
+        let mut buf = [0u8; 7];
+        buf.copy_from_slice(&self.raw[0x02..0x09]);
+        buf
+    }
+
+    /// Decoded alarm configuration (0x09-0x0C)
+    pub fn alarm(&self) -> Alarm {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(&self.raw[0x09..0x0D]);
+        decode_alarm(&buf)
+    }
+
+    /// Returns `true` if the register contents look like a PCF8563
+    ///
+    /// Rejects an all-0xFF read (typical of a floating bus or a different
+    /// device answering at the address). While the voltage-low flag is clear
+    /// the time registers must also hold valid, in-range BCD; after a power
+    /// loss they are undefined and are not checked.
+    pub fn is_plausible(&self) -> bool {
+        if self.raw.iter().all(|&b| b == 0xFF) {
+            return false;
+        }
+        if self.seconds().vl() {
+            return true;
+        }
+
+        invalid_datetime_field(&self.datetime_registers()).is_none()
+    }
+}
+
+impl core::fmt::Debug for RegisterSnapshot {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RegisterSnapshot")
+            .field("raw", &self.raw)
+            .field("control_status_1", &self.control_status_1())
+            .field("control_status_2", &self.control_status_2())
+            .field("voltage_low", &self.seconds().vl())
+            .field("century", &self.century_months().century())
+            .field("datetime", &self.datetime())
+            .field("alarm", &self.alarm())
+            .field("clkout_control", &self.clkout_control())
+            .field("timer_control", &self.timer_control())
+            .field("timer", &self.timer())
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for RegisterSnapshot {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "RegisterSnapshot {{ raw: {=[u8]:02x}, control_status_1: {}, control_status_2: {}, \
+             voltage_low: {}, century: {}, datetime: {}, alarm: {}, clkout_control: {}, \
+             timer_control: {}, timer: {} }}",
+            self.raw[..],
+            self.control_status_1(),
+            self.control_status_2(),
+            self.seconds().vl(),
+            self.century_months().century(),
+            self.datetime(),
+            self.alarm(),
+            self.clkout_control(),
+            self.timer_control(),
+            self.timer(),
+        )
+    }
+}
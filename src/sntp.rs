@@ -0,0 +1,265 @@
+use thiserror::Error;
+
+/// Size of an SNTP packet without extension fields or authenticator
+pub const NTP_PACKET_LEN: usize = 48;
+
+/// Seconds between the NTP epoch (1900-01-01) and the Unix epoch
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+
+/// 64-bit NTP timestamp: seconds since 1900 and a 32-bit binary fraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NtpTimestamp {
+    pub seconds: u32,
+    pub fraction: u32,
+}
+
+impl NtpTimestamp {
+    /// Timestamp for a Unix time in microseconds
+    pub const fn from_unix_micros(micros: i64) -> Self {
+        let secs = micros.div_euclid(1_000_000) + NTP_UNIX_OFFSET;
+        let micros = micros.rem_euclid(1_000_000) as u64;
+        Self {
+            // Wraps into era 1 after 2036
+            seconds: secs as u32,
+            fraction: ((micros << 32) / 1_000_000) as u32,
+        }
+    }
+
+    /// Unix time in microseconds
+    ///
+    /// Uses the RFC 4330 convention: timestamps with the top bit clear are
+    /// in era 1 (from 2036-02-07).
+    pub const fn to_unix_micros(&self) -> i64 {
+        let mut secs = self.seconds as i64;
+        if self.seconds & 0x8000_0000 == 0 {
+            secs += 1 << 32;
+        }
+        let micros = ((self.fraction as u64 * 1_000_000 + (1 << 31)) >> 32) as i64;
+        (secs - NTP_UNIX_OFFSET) * 1_000_000 + micros
+    }
+
+    /// Returns `true` for the all-zero "unknown" timestamp
+    pub const fn is_zero(&self) -> bool {
+        self.seconds == 0 && self.fraction == 0
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        Self {
+            seconds: u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            fraction: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+        }
+    }
+
+    fn write(&self, bytes: &mut [u8]) {
+        bytes[0..4].copy_from_slice(&self.seconds.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.fraction.to_be_bytes());
+    }
+}
+
+/// Leap second warning in the LI field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LeapIndicator {
+    NoWarning,
+    /// The last minute of the day has 61 seconds
+    InsertSecond,
+    /// The last minute of the day has 59 seconds
+    DeleteSecond,
+    /// The server clock is not synchronized
+    Unsynchronized,
+}
+
+/// Reason an SNTP response was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SntpError {
+    #[error("SNTP packet too short")]
+    Truncated,
+    /// Mode is not server (4); broadcast (5) replies are not accepted
+    #[error("Not an SNTP server reply")]
+    NotServerReply,
+    /// The originate timestamp does not echo our request
+    #[error("SNTP reply does not match request")]
+    UnexpectedOriginate,
+    /// Stratum 0 reply; the code is e.g. `*b"RATE"` or `*b"DENY"`
+    #[error("SNTP kiss-o'-death")]
+    KissOfDeath([u8; 4]),
+    #[error("SNTP server not synchronized")]
+    Unsynchronized,
+    #[error("Invalid SNTP stratum")]
+    InvalidStratum(u8),
+    /// The resulting time is outside the RTC's 2000-2099 range
+    #[error("SNTP time out of range")]
+    OutOfRange,
+}
+
+/// Decoded SNTP packet header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SntpPacket {
+    pub leap: LeapIndicator,
+    pub version: u8,
+    pub mode: u8,
+    pub stratum: u8,
+    pub poll: i8,
+    pub precision: i8,
+    /// Root delay, NTP short format (16.16 seconds)
+    pub root_delay: u32,
+    /// Root dispersion, NTP short format (16.16 seconds)
+    pub root_dispersion: u32,
+    /// Reference ID, or the kiss code for stratum 0
+    pub reference_id: [u8; 4],
+    pub reference: NtpTimestamp,
+    pub originate: NtpTimestamp,
+    pub receive: NtpTimestamp,
+    pub transmit: NtpTimestamp,
+}
+
+impl SntpPacket {
+    /// Decode the first 48 bytes of `bytes`; trailing extension fields are ignored
+    pub fn parse(bytes: &[u8]) -> Result<Self, SntpError> {
+        if bytes.len() < NTP_PACKET_LEN {
+            return Err(SntpError::Truncated);
+        }
+        Ok(Self {
+            leap: match bytes[0] >> 6 {
+                0 => LeapIndicator::NoWarning,
+                1 => LeapIndicator::InsertSecond,
+                2 => LeapIndicator::DeleteSecond,
+                _ => LeapIndicator::Unsynchronized,
+            },
+            version: (bytes[0] >> 3) & 0x07,
+            mode: bytes[0] & 0x07,
+            stratum: bytes[1],
+            poll: bytes[2] as i8,
+            precision: bytes[3] as i8,
+            root_delay: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            root_dispersion: u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+            reference_id: [bytes[12], bytes[13], bytes[14], bytes[15]],
+            reference: NtpTimestamp::read(&bytes[16..24]),
+            originate: NtpTimestamp::read(&bytes[24..32]),
+            receive: NtpTimestamp::read(&bytes[32..40]),
+            transmit: NtpTimestamp::read(&bytes[40..48]),
+        })
+    }
+
+    /// Kiss-o'-death code, if this is a stratum 0 reply
+    pub fn kiss_code(&self) -> Option<[u8; 4]> {
+        (self.stratum == 0).then_some(self.reference_id)
+    }
+}
+
+/// Build an SNTPv4 client request carrying `transmit` as its transmit timestamp
+pub fn build_sntp_request(transmit: NtpTimestamp) -> [u8; NTP_PACKET_LEN] {
+    let mut packet = [0u8; NTP_PACKET_LEN];
+    packet[0] = (4 << 3) | 3; // LI = 0, VN = 4, Mode = 3 (client)
+    transmit.write(&mut packet[40..48]);
+    packet
+}
+
+/// Clock offset and round-trip delay from one SNTP exchange
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SntpMeasurement {
+    /// Server time minus local time, in microseconds
+    pub offset_micros: i64,
+    /// Round-trip network delay, in microseconds
+    pub delay_micros: i64,
+    pub stratum: u8,
+    pub leap: LeapIndicator,
+}
+
+/// Validate a server reply and compute offset and delay
+///
+/// `sent` is the transmit timestamp of our request (T1) and `received`
+/// the local time at which the reply arrived (T4). Broadcast packets,
+/// kiss-o'-death replies, unsynchronized servers and replies not echoing
+/// `sent` are rejected.
+pub fn process_sntp_response(
+    response: &[u8],
+    sent: NtpTimestamp,
+    received: NtpTimestamp,
+) -> Result<SntpMeasurement, SntpError> {
+    let packet = SntpPacket::parse(response)?;
+    if packet.mode != 4 {
+        return Err(SntpError::NotServerReply);
+    }
+    if let Some(code) = packet.kiss_code() {
+        return Err(SntpError::KissOfDeath(code));
+    }
+    if packet.stratum > 15 {
+        return Err(SntpError::InvalidStratum(packet.stratum));
+    }
+    if packet.leap == LeapIndicator::Unsynchronized || packet.transmit.is_zero() {
+        return Err(SntpError::Unsynchronized);
+    }
+    if packet.originate != sent {
+        return Err(SntpError::UnexpectedOriginate);
+    }
+
+    let t1 = sent.to_unix_micros();
+    let t2 = packet.receive.to_unix_micros();
+    let t3 = packet.transmit.to_unix_micros();
+    let t4 = received.to_unix_micros();
+    Ok(SntpMeasurement {
+        offset_micros: ((t2 - t1) + (t3 - t4)) / 2,
+        delay_micros: (t4 - t1) - (t3 - t2),
+        stratum: packet.stratum,
+        leap: packet.leap,
+    })
+}
+
+/// Outstanding SNTP request with the local clock it was stamped from
+///
+/// Created by `sntp_request()`. Local time is the RTC reading extended by
+/// the MCU monotonic clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SntpRequest {
+    packet: [u8; NTP_PACKET_LEN],
+    sent: NtpTimestamp,
+    base_micros: i64,
+    base_ticks: u64,
+    tick_hz: u32,
+}
+
+impl SntpRequest {
+    /// Request stamped with `base_micros`, the local time at `base_ticks`
+    pub(crate) fn new(base_micros: i64, base_ticks: u64, tick_hz: u32) -> Self {
+        let sent = NtpTimestamp::from_unix_micros(base_micros);
+        Self {
+            packet: build_sntp_request(sent),
+            sent,
+            base_micros,
+            base_ticks,
+            tick_hz,
+        }
+    }
+
+    /// Packet to send to the server (UDP port 123)
+    pub fn packet(&self) -> &[u8; NTP_PACKET_LEN] {
+        &self.packet
+    }
+
+    /// Transmit timestamp (T1) carried in the packet
+    pub fn sent(&self) -> NtpTimestamp {
+        self.sent
+    }
+
+    /// Local Unix time in microseconds at MCU tick count `ticks`
+    pub fn local_micros_at(&self, ticks: u64) -> i64 {
+        let elapsed = ticks.wrapping_sub(self.base_ticks) as i128;
+        self.base_micros + (elapsed * 1_000_000 / self.tick_hz as i128) as i64
+    }
+
+    /// Validate `response`, received at MCU tick count `received_ticks`
+    pub fn process(
+        &self,
+        response: &[u8],
+        received_ticks: u64,
+    ) -> Result<SntpMeasurement, SntpError> {
+        let received = NtpTimestamp::from_unix_micros(self.local_micros_at(received_ticks));
+        process_sntp_response(response, self.sent, received)
+    }
+}
@@ -0,0 +1,21 @@
+use crate::RtcError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EdgeError<I2cErr, PinErr> {
+    #[error("RTC error")]
+    Rtc(RtcError<I2cErr>),
+    #[error("Edge input error")]
+    Pin(PinErr),
+    /// No rising edge within the timeout (e.g. PPS not wired)
+    #[error("Timed out waiting for an edge")]
+    Timeout,
+}
+
+/// Delay from a second boundary to releasing STOP, in microseconds
+///
+/// After STOP is cleared the first one-second increment follows after
+/// 0.507813 to 0.507935 s, so releasing it this long after a boundary makes
+/// the first increment land on the next boundary (within ±61 µs).
+pub const STOP_RELEASE_DELAY_US: u32 = 1_000_000 - 507_874;
@@ -0,0 +1,24 @@
+use crate::DateTime;
+
+/// Date and time extended with milliseconds from an MCU monotonic clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SubsecDateTime {
+    /// Date and time as read from the RTC
+    pub datetime: DateTime,
+    /// Milliseconds since the last RTC seconds increment (0-999)
+    pub millis: u16,
+}
+
+/// MCU tick count observed at an RTC seconds increment
+#[derive(Clone, Copy)]
+pub(crate) struct SubsecAnchor {
+    pub(crate) ticks: u64,
+    pub(crate) seconds: u8,
+}
+
+/// Seconds after which `get_datetime_with_subsec()` re-syncs to the RTC
+///
+/// Bounds the phase error from MCU clock drift, e.g. 1.5 ms for a 50 ppm
+/// MCU oscillator.
+pub const SUBSEC_RESYNC_SECS: u32 = 30;
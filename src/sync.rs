@@ -0,0 +1,22 @@
+use crate::RtcError;
+use thiserror::Error;
+
+/// Result of syncing the RTC to an external time reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SyncOutcome {
+    /// The RTC was set; `offset_secs` is the previous RTC reading minus the
+    /// reference, or `None` if the RTC held no valid time
+    Updated { offset_secs: Option<i64> },
+    /// The RTC was within the threshold and left untouched
+    InSync { offset_secs: i64 },
+}
+
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SyncError<I2cErr, SourceErr> {
+    #[error("RTC error")]
+    Rtc(RtcError<I2cErr>),
+    #[error("Time source error")]
+    Source(SourceErr),
+}
@@ -0,0 +1,106 @@
+use crate::drift::round;
+
+/// Parabolic frequency-vs-temperature curve of a 32.768 kHz tuning-fork crystal
+///
+/// `ppm = -curvature * (T - turnover)²`: the crystal is fastest at the
+/// turnover temperature and runs slow on either side of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TemperatureModel {
+    /// Turnover temperature in °C
+    pub turnover_c: f32,
+    /// Curvature coefficient in ppm/°C²
+    pub curvature_ppm_per_c2: f32,
+}
+
+impl Default for TemperatureModel {
+    /// Typical tuning-fork crystal: 25 °C turnover, 0.034 ppm/°C²
+    fn default() -> Self {
+        Self {
+            turnover_c: 25.0,
+            curvature_ppm_per_c2: 0.034,
+        }
+    }
+}
+
+impl TemperatureModel {
+    /// Frequency error at `celsius` in ppm (positive: RTC runs fast)
+    pub fn ppm_at(&self, celsius: f32) -> f32 {
+        let delta = celsius - self.turnover_c;
+        -self.curvature_ppm_per_c2 * delta * delta
+    }
+}
+
+/// Temperature reading taken at an RTC time
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TemperatureSample {
+    /// RTC reading (Unix timestamp) when the temperature was measured
+    pub timestamp: i64,
+    /// Temperature in °C
+    pub celsius: f32,
+}
+
+/// Integrates the expected crystal error from periodic temperature samples
+///
+/// Between two samples the error rate is interpolated linearly; after the
+/// last sample its temperature is assumed to persist. Like
+/// [`DriftParams`](crate::DriftParams), this is plain data for the caller to
+/// persist.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TemperatureCompensation {
+    /// Crystal model
+    pub model: TemperatureModel,
+    /// Error accumulated up to `last_sample`, in ms (positive: RTC ahead)
+    pub accumulated_ms: f64,
+    /// Most recent temperature sample
+    pub last_sample: Option<TemperatureSample>,
+}
+
+impl TemperatureCompensation {
+    /// Start integrating with `model` and no samples
+    pub const fn new(model: TemperatureModel) -> Self {
+        Self {
+            model,
+            accumulated_ms: 0.0,
+            last_sample: None,
+        }
+    }
+
+    /// Add a temperature sample taken at RTC time `timestamp`
+    ///
+    /// Samples older than the previous one are ignored.
+    pub fn add_sample(&mut self, timestamp: i64, celsius: f32) {
+        if let Some(last) = self.last_sample {
+            if timestamp < last.timestamp {
+                return;
+            }
+            let ppm = (self.model.ppm_at(last.celsius) + self.model.ppm_at(celsius)) / 2.0;
+            self.accumulated_ms += (timestamp - last.timestamp) as f64 * ppm as f64 / 1000.0;
+        }
+        self.last_sample = Some(TemperatureSample { timestamp, celsius });
+    }
+
+    /// Expected error of an RTC reading, in ms (positive: RTC ahead)
+    pub fn error_ms(&self, raw_timestamp: i64) -> i64 {
+        let pending = match self.last_sample {
+            Some(last) if raw_timestamp > last.timestamp => {
+                (raw_timestamp - last.timestamp) as f64 * self.model.ppm_at(last.celsius) as f64
+                    / 1000.0
+            }
+            _ => 0.0,
+        };
+        round(self.accumulated_ms + pending)
+    }
+
+    /// Restart integration at RTC time `timestamp`, e.g. after a sync
+    ///
+    /// The last temperature is kept as the starting point.
+    pub fn reset(&mut self, timestamp: i64) {
+        self.accumulated_ms = 0.0;
+        if let Some(last) = self.last_sample.as_mut() {
+            last.timestamp = timestamp;
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::PorOverride;
+
+/// State of the TEST1 and TESTC bits in Control_status_1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TestModeStatus {
+    /// EXT_CLK test mode (TEST1) is active
+    pub ext_clk_test_mode: bool,
+    /// Power-On Reset (POR) override facility state (TESTC)
+    pub por_override: PorOverride,
+}
+
+impl TestModeStatus {
+    /// Returns `true` if both TEST1 and TESTC are set for normal operation
+    pub fn is_normal(&self) -> bool {
+        !self.ext_clk_test_mode && self.por_override == PorOverride::Disabled
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{DateTime, calendar};
+use thiserror::Error;
+
+/// Reason a textual date did not parse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DateParseError {
+    #[error("Malformed date")]
+    Malformed,
+    /// Date or time invalid, or the UTC result is outside 2000-2099
+    #[error("Date out of range")]
+    OutOfRange,
+}
+
+/// Largest time zone offset in use (UTC+14, Line Islands)
+const MAX_ZONE_OFFSET_SECS: i64 = 14 * 3600;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const LONG_DAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Parse an HTTP `Date` header value (RFC 7231 section 7.1.1.1)
+///
+/// Accepts the preferred IMF-fixdate (`Sun, 06 Nov 1994 08:49:37 GMT`)
+/// and the obsolete RFC 850 (`Sunday, 06-Nov-94 08:49:37 GMT`) and asctime
+/// (`Sun Nov  6 08:49:37 1994`) formats, with or without a leading
+/// `Date:`. Two-digit RFC 850 years are taken as 20xx. A leap second (60)
+/// is clamped to 59.
+pub fn parse_http_date(value: &str) -> Result<DateTime, DateParseError> {
+    let value = strip_prefix_ignore_case(value.trim(), "Date:").trim_start();
+    let mut tokens = value.split_ascii_whitespace();
+    let first = tokens.next().ok_or(DateParseError::Malformed)?;
+
+    let (year, month, day, time) = if let Some(name) = first.strip_suffix(',') {
+        if DAYS.contains(&name) {
+            // IMF-fixdate: 06 Nov 1994 08:49:37 GMT
+            let day = fixed(next(&mut tokens)?, 2)?;
+            let month = month(next(&mut tokens)?)?;
+            let year = fixed(next(&mut tokens)?, 4)?;
+            (year as u16, month, day as u8, next(&mut tokens)?)
+        } else if LONG_DAYS.contains(&name) {
+            // RFC 850: 06-Nov-94 08:49:37 GMT
+            let mut date = next(&mut tokens)?.split('-');
+            let day = fixed(date.next().ok_or(DateParseError::Malformed)?, 2)?;
+            let month = month(date.next().ok_or(DateParseError::Malformed)?)?;
+            let year = fixed(date.next().ok_or(DateParseError::Malformed)?, 2)?;
+            if date.next().is_some() {
+                return Err(DateParseError::Malformed);
+            }
+            (2000 + year as u16, month, day as u8, next(&mut tokens)?)
+        } else {
+            return Err(DateParseError::Malformed);
+        }
+    } else if DAYS.contains(&first) {
+        // asctime: Nov  6 08:49:37 1994
+        let month = month(next(&mut tokens)?)?;
+        let day = number(next(&mut tokens)?, 1, 2)?;
+        let time = next(&mut tokens)?;
+        let year = fixed(next(&mut tokens)?, 4)?;
+        if tokens.next().is_some() {
+            return Err(DateParseError::Malformed);
+        }
+        let (hours, minutes, seconds) = time_of_day(time, false)?;
+        return local_to_utc(year as u16, month, day as u8, hours, minutes, seconds, 0);
+    } else {
+        return Err(DateParseError::Malformed);
+    };
+
+    if next(&mut tokens)? != "GMT" || tokens.next().is_some() {
+        return Err(DateParseError::Malformed);
+    }
+    let (hours, minutes, seconds) = time_of_day(time, false)?;
+    local_to_utc(year, month, day, hours, minutes, seconds, 0)
+}
+
+/// Parse an RFC 2822 (Internet Message Format) date-time and convert it to UTC
+///
+/// `[Fri, ]21 Nov 1997 09:55:06 -0600`: the weekday and seconds are
+/// optional; the zone may be numeric or one of the obsolete names (`UT`,
+/// `GMT`, `EST`, `EDT`, `CST`, `CDT`, `MST`, `MDT`, `PST`, `PDT`, or a
+/// military letter, taken as UTC); numeric zones beyond ±14:00 are
+/// rejected. Two- and three-digit years follow the RFC's obsolete-syntax
+/// rules. Comments are not supported.
+pub fn parse_rfc2822(value: &str) -> Result<DateTime, DateParseError> {
+    let mut tokens = value.split_ascii_whitespace().peekable();
+    if let Some(first) = tokens.peek()
+        && let Some(name) = first.strip_suffix(',')
+    {
+        if !DAYS.contains(&name) {
+            return Err(DateParseError::Malformed);
+        }
+        tokens.next();
+    }
+
+    let day = number(next(&mut tokens)?, 1, 2)? as u8;
+    let month = month(next(&mut tokens)?)?;
+    let year_token = next(&mut tokens)?;
+    let year = number(year_token, 2, 4)?;
+    let year = match year_token.len() {
+        2 if year < 50 => 2000 + year,
+        2 | 3 => 1900 + year,
+        _ => year,
+    };
+    let (hours, minutes, seconds) = time_of_day(next(&mut tokens)?, true)?;
+    let offset_secs = match next(&mut tokens)? {
+        "UT" | "GMT" => 0,
+        "EDT" => -4 * 3600,
+        "EST" | "CDT" => -5 * 3600,
+        "CST" | "MDT" => -6 * 3600,
+        "MST" | "PDT" => -7 * 3600,
+        "PST" => -8 * 3600,
+        zone if zone.len() == 1 && zone.bytes().all(|b| b.is_ascii_alphabetic()) => 0,
+        zone => {
+            let (sign, digits) = zone.split_at_checked(1).ok_or(DateParseError::Malformed)?;
+            let hhmm = fixed(digits, 4)? as i64;
+            let offset = (hhmm / 100) * 3600 + (hhmm % 100) * 60;
+            if hhmm % 100 > 59 || offset > MAX_ZONE_OFFSET_SECS {
+                return Err(DateParseError::Malformed);
+            }
+            match sign {
+                "+" => offset,
+                "-" => -offset,
+                _ => return Err(DateParseError::Malformed),
+            }
+        }
+    };
+    if tokens.next().is_some() {
+        return Err(DateParseError::Malformed);
+    }
+    local_to_utc(
+        year as u16,
+        month,
+        day,
+        hours,
+        minutes,
+        seconds,
+        offset_secs,
+    )
+}
+
+/// Parse a 3GPP TS 27.007 `AT+CCLK?` response and convert it to UTC
+///
+/// `+CCLK: "24/07/04,20:15:30+08"`: the time zone is in quarter hours, at
+/// most ±56 (±14 h). The
+/// `+CCLK:` prefix, the quotes and the zone are optional, and four-digit
+/// years are accepted, to cope with modem variations. Two-digit years are
+/// taken as 20xx.
+pub fn parse_cclk(response: &str) -> Result<DateTime, DateParseError> {
+    let value = response.trim();
+    let value = value.strip_prefix("+CCLK:").unwrap_or(value).trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+
+    let (date, time) = value.split_once(',').ok_or(DateParseError::Malformed)?;
+    let mut date = date.split('/');
+    let year_token = date.next().ok_or(DateParseError::Malformed)?;
+    let year = number(year_token, 2, 4)?;
+    let year = if year_token.len() == 2 {
+        2000 + year
+    } else {
+        year
+    };
+    let month = fixed(date.next().ok_or(DateParseError::Malformed)?, 2)? as u8;
+    let day = fixed(date.next().ok_or(DateParseError::Malformed)?, 2)? as u8;
+    if date.next().is_some() {
+        return Err(DateParseError::Malformed);
+    }
+
+    let (time, offset_secs) = match time.find(['+', '-']) {
+        Some(index) => {
+            let (time, zone) = time.split_at(index);
+            let quarters = number(&zone[1..], 1, 2)? as i64;
+            let offset = quarters * 15 * 60;
+            if offset > MAX_ZONE_OFFSET_SECS {
+                return Err(DateParseError::Malformed);
+            }
+            (
+                time,
+                if zone.starts_with('-') {
+                    -offset
+                } else {
+                    offset
+                },
+            )
+        }
+        None => (time, 0),
+    };
+    let (hours, minutes, seconds) = time_of_day(time, false)?;
+    local_to_utc(
+        year as u16,
+        month,
+        day,
+        hours,
+        minutes,
+        seconds,
+        offset_secs,
+    )
+}
+
+fn next<'a>(tokens: &mut impl Iterator<Item = &'a str>) -> Result<&'a str, DateParseError> {
+    tokens.next().ok_or(DateParseError::Malformed)
+}
+
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> &'a str {
+    match value.get(..prefix.len()) {
+        Some(head) if head.eq_ignore_ascii_case(prefix) => &value[prefix.len()..],
+        _ => value,
+    }
+}
+
+fn month(token: &str) -> Result<u8, DateParseError> {
+    MONTHS
+        .iter()
+        .position(|&m| m == token)
+        .map(|index| index as u8 + 1)
+        .ok_or(DateParseError::Malformed)
+}
+
+/// `hh:mm:ss`, or `hh:mm[:ss]` if `optional_seconds`; 60 s is clamped to 59
+fn time_of_day(token: &str, optional_seconds: bool) -> Result<(u8, u8, u8), DateParseError> {
+    let mut parts = token.split(':');
+    let hours = fixed(parts.next().ok_or(DateParseError::Malformed)?, 2)? as u8;
+    let minutes = fixed(parts.next().ok_or(DateParseError::Malformed)?, 2)? as u8;
+    let seconds = match parts.next() {
+        Some(seconds) => fixed(seconds, 2)? as u8,
+        None if optional_seconds => 0,
+        None => return Err(DateParseError::Malformed),
+    };
+    if parts.next().is_some() || seconds > 60 {
+        return Err(DateParseError::Malformed);
+    }
+    Ok((hours, minutes, seconds.min(59)))
+}
+
+/// Exactly `len` ASCII digits
+fn fixed(token: &str, len: usize) -> Result<u32, DateParseError> {
+    number(token, len, len)
+}
+
+/// Between `min` and `max` ASCII digits
+fn number(token: &str, min: usize, max: usize) -> Result<u32, DateParseError> {
+    if token.len() < min || token.len() > max || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(DateParseError::Malformed);
+    }
+    Ok(token.bytes().fold(0, |acc, b| acc * 10 + (b - b'0') as u32))
+}
+
+/// Validate a local date-time and shift it to UTC
+fn local_to_utc(
+    year: u16,
+    month: u8,
+    day: u8,
+    hours: u8,
+    minutes: u8,
+    seconds: u8,
+    offset_secs: i64,
+) -> Result<DateTime, DateParseError> {
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > calendar::days_in_month(year, month)
+        || hours > 23
+        || minutes > 59
+    {
+        return Err(DateParseError::OutOfRange);
+    }
+    let days = calendar::days_from_civil(year, month, day);
+    let local = days * 86_400 + hours as i64 * 3600 + minutes as i64 * 60 + seconds as i64;
+    DateTime::from_unix_timestamp(local - offset_secs).ok_or(DateParseError::OutOfRange)
+}
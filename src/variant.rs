@@ -0,0 +1,18 @@
+/// RTC chip fitted at the bus address, as reported by `detect_variant()`
+///
+/// The NXP PCF8563 and the BM8563 (and other clones) document the same
+/// register map and reset values. Until a readback difference has been
+/// measured on both chips, `detect_variant()` only returns
+/// [`Unknown`](Self::Unknown); the other variants are reserved for a
+/// confirmed check, so do not rely on them being returned today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Variant {
+    /// NXP PCF8563 (not returned until a distinguishing check is confirmed)
+    Pcf8563,
+    /// BM8563 or another clone (not returned until a distinguishing check
+    /// is confirmed)
+    Bm8563,
+    /// The chips could not be told apart
+    Unknown,
+}
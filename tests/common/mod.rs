@@ -0,0 +1,132 @@
+//! Shared helpers for the host tests
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation};
+use pcf8563_dd::Monotonic;
+
+/// In-memory PCF8563 register file with auto-incrementing register pointer
+///
+/// Clones share the registers, so a test can keep one handle to inspect or
+/// advance the clock while the driver owns another.
+#[derive(Clone, Default)]
+pub struct MockRtc {
+    regs: Rc<RefCell<[u8; 16]>>,
+    writes: Rc<Cell<usize>>,
+}
+
+#[allow(dead_code)]
+impl MockRtc {
+    pub fn new(regs: [u8; 16]) -> Self {
+        Self {
+            regs: Rc::new(RefCell::new(regs)),
+            writes: Rc::default(),
+        }
+    }
+
+    pub fn regs(&self) -> [u8; 16] {
+        *self.regs.borrow()
+    }
+
+    /// Number of write operations that carried register data
+    pub fn writes(&self) -> usize {
+        self.writes.get()
+    }
+
+    /// Load the time registers (0x02-0x08) from BCD values
+    pub fn set_time_registers(&self, time: [u8; 7]) {
+        self.regs.borrow_mut()[2..9].copy_from_slice(&time);
+    }
+}
+
+impl ErrorType for MockRtc {
+    type Error = ErrorKind;
+}
+
+impl I2c for MockRtc {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut regs = self.regs.borrow_mut();
+        let mut pointer = 0;
+        for (index, operation) in operations.iter_mut().enumerate() {
+            match operation {
+                Operation::Write(data) => {
+                    let data = if index == 0 {
+                        pointer = data[0] as usize;
+                        &data[1..]
+                    } else {
+                        &data[..]
+                    };
+                    if !data.is_empty() {
+                        self.writes.set(self.writes.get() + 1);
+                    }
+                    for &byte in data {
+                        regs[pointer % 16] = byte;
+                        pointer += 1;
+                    }
+                }
+                Operation::Read(buf) => {
+                    for byte in buf.iter_mut() {
+                        *byte = regs[pointer % 16];
+                        pointer += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 1 MHz MCU clock that advances by `step_us` on every reading and by the
+/// requested time on every delay
+///
+/// Clones share the tick count, so one handle can serve as the
+/// [`Monotonic`] and another as the [`DelayNs`] of the same call.
+#[derive(Clone)]
+pub struct FakeClock {
+    ticks: Rc<Cell<u64>>,
+    step_us: u64,
+}
+
+#[allow(dead_code)]
+impl FakeClock {
+    pub fn new(step_us: u64) -> Self {
+        Self {
+            ticks: Rc::new(Cell::new(0)),
+            step_us,
+        }
+    }
+
+    /// Current tick count without advancing the clock
+    pub fn ticks(&self) -> u64 {
+        self.ticks.get()
+    }
+
+    /// Advance the clock by `us` and return the new tick count
+    pub fn now_after(&mut self, us: u64) -> u64 {
+        self.ticks.set(self.ticks.get() + us);
+        self.ticks.get()
+    }
+}
+
+impl Monotonic for FakeClock {
+    fn now(&mut self) -> u64 {
+        self.ticks.set(self.ticks.get() + self.step_us);
+        self.ticks.get()
+    }
+
+    fn tick_hz(&self) -> u32 {
+        1_000_000
+    }
+}
+
+impl DelayNs for FakeClock {
+    fn delay_ns(&mut self, ns: u32) {
+        self.ticks.set(self.ticks.get() + ns.div_ceil(1000) as u64);
+    }
+}
@@ -0,0 +1,205 @@
+mod common;
+
+use common::MockRtc;
+use pcf8563_dd::{
+    DateTime, DriftCompensated, DriftParams, Pcf8563, TemperatureCompensation, TemperatureModel,
+    TemperatureSample,
+};
+
+/// 2024-03-15 12:00:00 (Friday)
+const START: i64 = 1_710_504_000;
+/// One day
+const DAY: i64 = 86_400;
+
+fn datetime(timestamp: i64) -> DateTime {
+    DateTime::from_unix_timestamp(timestamp).unwrap()
+}
+
+fn bcd(value: u8) -> u8 {
+    ((value / 10) << 4) | (value % 10)
+}
+
+/// Time registers for `timestamp`, with the VL flag set if `voltage_low`
+fn time_registers(timestamp: i64, voltage_low: bool) -> [u8; 7] {
+    let dt = datetime(timestamp);
+    [
+        bcd(dt.seconds) | if voltage_low { 0x80 } else { 0 },
+        bcd(dt.minutes),
+        bcd(dt.hours),
+        bcd(dt.day),
+        dt.weekday,
+        bcd(dt.month),
+        bcd(dt.year),
+    ]
+}
+
+/// 20 °C turnover, 0.04 ppm/°C²: -4 ppm at 30 °C, -16 ppm at 40 °C
+const MODEL: TemperatureModel = TemperatureModel {
+    turnover_c: 20.0,
+    curvature_ppm_per_c2: 0.04,
+};
+
+fn compensated(
+    mock: &MockRtc,
+    params: DriftParams,
+) -> DriftCompensated<pcf8563_dd::Pcf8563Interface<MockRtc>, embedded_hal::i2c::ErrorKind> {
+    DriftCompensated::new(Pcf8563::new(mock.clone()), params)
+}
+
+#[test]
+fn sample_from_successive_syncs() {
+    let mock = MockRtc::default();
+    let mut rtc = compensated(&mock, DriftParams::new(0.0));
+    assert_eq!(rtc.set_datetime(&datetime(START)).unwrap(), None);
+
+    // The RTC gained 2 s over 10 days: +2.3 ppm
+    mock.set_time_registers(time_registers(START + 10 * DAY + 2, false));
+    let sample = rtc
+        .set_datetime(&datetime(START + 10 * DAY))
+        .unwrap()
+        .unwrap();
+    assert_eq!(sample.interval_secs, 10 * DAY as u32);
+    assert!((sample.ppm - 2.3148).abs() < 0.001, "{sample:?}");
+}
+
+#[test]
+fn no_sample_without_earlier_sync() {
+    let mock = MockRtc::default();
+    mock.set_time_registers(time_registers(START + 5, false));
+    let mut rtc = compensated(&mock, DriftParams::new(0.0));
+
+    assert_eq!(rtc.set_datetime(&datetime(START)).unwrap(), None);
+    assert_eq!(rtc.params().last_reference, Some(START));
+}
+
+#[test]
+fn no_sample_when_voltage_low() {
+    let mock = MockRtc::default();
+    // Valid BCD after a power loss, but VL is set
+    mock.set_time_registers(time_registers(START + 3_600, true));
+    let mut rtc = compensated(&mock, DriftParams::synced_at(0.0, START - DAY));
+
+    assert_eq!(rtc.set_datetime(&datetime(START)).unwrap(), None);
+    assert_eq!(mock.regs()[2] & 0x80, 0, "set_datetime clears VL");
+}
+
+#[test]
+fn no_correction_before_first_sync() {
+    let mock = MockRtc::default();
+    mock.set_time_registers(time_registers(START, false));
+    let mut rtc = compensated(&mock, DriftParams::new(-23.5));
+    rtc.set_writeback_threshold(Some(1000));
+
+    assert_eq!(rtc.get_datetime().unwrap(), datetime(START));
+    assert_eq!(&mock.regs()[2..9], &time_registers(START, false));
+}
+
+#[test]
+fn temperature_model_curve() {
+    let model = TemperatureModel::default();
+    assert_eq!(model.ppm_at(25.0), 0.0);
+    assert!((model.ppm_at(35.0) - -3.4).abs() < 1e-4);
+    assert!((model.ppm_at(15.0) - -3.4).abs() < 1e-4);
+
+    assert_eq!(MODEL.ppm_at(20.0), 0.0);
+    assert!((MODEL.ppm_at(30.0) - -4.0).abs() < 1e-4);
+    assert!((MODEL.ppm_at(0.0) - -16.0).abs() < 1e-4);
+}
+
+#[test]
+fn temperature_error_integration() {
+    let mut temperature = TemperatureCompensation::new(MODEL);
+    assert_eq!(temperature.error_ms(START + DAY), 0);
+
+    // One day at 30 °C: -4 ppm * 86 400 s = -345.6 ms
+    temperature.add_sample(START, 30.0);
+    temperature.add_sample(START + DAY, 30.0);
+    assert!((temperature.accumulated_ms - -345.6).abs() < 1e-3);
+    assert_eq!(temperature.error_ms(START + DAY), -346);
+    // The last temperature persists past the last sample
+    assert_eq!(temperature.error_ms(START + 2 * DAY), -691);
+
+    // Linear ramp from 30 °C to 20 °C: -2 ppm on average, -172.8 ms
+    temperature.add_sample(START + 2 * DAY, 20.0);
+    assert_eq!(temperature.error_ms(START + 2 * DAY), -518);
+    assert_eq!(temperature.error_ms(START + 3 * DAY), -518);
+
+    // Out-of-order samples are ignored
+    temperature.add_sample(START, 40.0);
+    assert_eq!(temperature.error_ms(START + 3 * DAY), -518);
+
+    temperature.reset(START + 3 * DAY);
+    assert_eq!(temperature.accumulated_ms, 0.0);
+    assert_eq!(
+        temperature.last_sample,
+        Some(TemperatureSample {
+            timestamp: START + 3 * DAY,
+            celsius: 20.0,
+        })
+    );
+    assert_eq!(temperature.error_ms(START + 4 * DAY), 0);
+}
+
+#[test]
+fn temperature_correction_and_writeback() {
+    let mock = MockRtc::default();
+    let mut rtc = compensated(&mock, DriftParams::new(0.0));
+    rtc.set_temperature_compensation(Some(TemperatureCompensation::new(MODEL)));
+    rtc.set_datetime(&datetime(START)).unwrap();
+    rtc.add_temperature_sample(40.0).unwrap();
+
+    // Ten days at 40 °C (-16 ppm): the RTC fell 13.824 s behind
+    mock.set_time_registers(time_registers(START + 10 * DAY - 14, false));
+    assert_eq!(rtc.get_datetime().unwrap(), datetime(START + 10 * DAY));
+    assert_eq!(
+        &mock.regs()[2..9],
+        &time_registers(START + 10 * DAY - 14, false),
+        "no write-back without a threshold"
+    );
+
+    rtc.set_writeback_threshold(Some(1000));
+    assert_eq!(rtc.get_datetime().unwrap(), datetime(START + 10 * DAY));
+    assert_eq!(&mock.regs()[2..9], &time_registers(START + 10 * DAY, false));
+    let params = rtc.params();
+    assert_eq!(params.last_sync, Some(START + 10 * DAY));
+    // -13 823.776 ms rounds to -13 824 ms, of which -14 s were applied
+    assert_eq!(params.residual_ms, 176);
+
+    // The integrator restarted at the corrected time, keeping the temperature
+    let temperature = rtc.temperature_compensation().unwrap();
+    assert_eq!(temperature.accumulated_ms, 0.0);
+    assert_eq!(
+        temperature.last_sample,
+        Some(TemperatureSample {
+            timestamp: START + 10 * DAY,
+            celsius: 40.0,
+        })
+    );
+    let writes = mock.writes();
+    assert_eq!(rtc.get_datetime().unwrap(), datetime(START + 10 * DAY));
+    assert_eq!(mock.writes(), writes, "correction applied twice");
+}
+
+#[test]
+fn temperature_reset_on_sync() {
+    let mock = MockRtc::default();
+    let mut rtc = compensated(&mock, DriftParams::new(0.0));
+    rtc.set_temperature_compensation(Some(TemperatureCompensation::new(MODEL)));
+    rtc.set_datetime(&datetime(START)).unwrap();
+    rtc.add_temperature_sample(30.0).unwrap();
+    mock.set_time_registers(time_registers(START + DAY, false));
+    rtc.add_temperature_sample(30.0).unwrap();
+    assert!((rtc.temperature_compensation().unwrap().accumulated_ms - -345.6).abs() < 1e-3);
+
+    rtc.set_datetime(&datetime(START + DAY)).unwrap();
+    let temperature = rtc.temperature_compensation().unwrap();
+    assert_eq!(temperature.accumulated_ms, 0.0);
+    assert_eq!(
+        temperature.last_sample,
+        Some(TemperatureSample {
+            timestamp: START + DAY,
+            celsius: 30.0,
+        })
+    );
+    assert_eq!(rtc.get_datetime().unwrap(), datetime(START + DAY));
+}
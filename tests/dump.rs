@@ -0,0 +1,33 @@
+mod common;
+
+use common::MockRtc;
+use pcf8563_dd::Pcf8563;
+
+#[test]
+fn dump_decodes_fields() {
+    let regs = [
+        0x08, 0x1F, 0x25, 0x37, 0x20, 0x04, 0x04, 0x87, 0x24, 0xB7, 0x12, 0x80, 0x05, 0x81, 0x82,
+        0x10,
+    ];
+    let mut rtc = Pcf8563::new(MockRtc::new(regs));
+    let snapshot = rtc.read_snapshot().unwrap();
+
+    let expected = "\
+0x00 control_status_1 = 0x08 TEST1=normal STOP=running TESTC=enabled
+0x01 control_status_2 = 0x1F TI_TP=pulse AF=set TF=set AIE=enabled TIE=enabled
+0x02 seconds          = 0x25 VL=clear second=25
+0x03 minutes          = 0x37 minute=37
+0x04 hours            = 0x20 hour=20
+0x05 days             = 0x04 day=4
+0x06 weekdays         = 0x04 weekday=4
+0x07 century_months   = 0x87 C=1 month=7
+0x08 years            = 0x24 year=24
+0x09 minute_alarm     = 0xB7 AE_M=disabled minute=37
+0x0A hour_alarm       = 0x12 AE_H=enabled hour=12
+0x0B day_alarm        = 0x80 AE_D=disabled day=0
+0x0C weekday_alarm    = 0x05 AE_W=enabled weekday=5
+0x0D clkout_control   = 0x81 FE=enabled FD=1024Hz
+0x0E timer_control    = 0x82 TE=enabled TD=1Hz
+0x0F timer            = 0x10 timer=16";
+    assert_eq!(snapshot.dump().to_string(), expected);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::MockRtc;
+use pcf8563_dd::{DateTime, NmeaError, Pcf8563, SyncError, SyncOutcome, parse_nmea};
+
+/// Wrap a sentence body in `$...*hh` with a correct checksum
+fn sentence(body: &str) -> String {
+    let checksum = body.bytes().fold(0, |acc, b| acc ^ b);
+    format!("${body}*{checksum:02X}")
+}
+
+fn datetime(year: u8, month: u8, day: u8, hours: u8, minutes: u8, seconds: u8) -> DateTime {
+    let timestamp = DateTime {
+        year,
+        month,
+        day,
+        hours,
+        minutes,
+        seconds,
+        weekday: 0,
+    }
+    .to_unix_timestamp();
+    DateTime::from_unix_timestamp(timestamp).unwrap()
+}
+
+#[test]
+fn rmc() {
+    let rmc = "$GPRMC,123519,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W*6A";
+    // Two-digit RMC years are always taken as 20yy
+    assert_eq!(parse_nmea(rmc), Ok(datetime(94, 3, 23, 12, 35, 19)));
+
+    let rmc = sentence("GNRMC,083559.00,A,4717.11437,N,00833.91522,E,0.004,77.52,150324,,,A");
+    assert_eq!(parse_nmea(&rmc), Ok(datetime(24, 3, 15, 8, 35, 59)));
+}
+
+#[test]
+fn zda() {
+    let zda = sentence("GPZDA,201530.00,04,07,2024,00,00");
+    assert_eq!(parse_nmea(&zda), Ok(datetime(24, 7, 4, 20, 15, 30)));
+}
+
+#[test]
+fn bad_checksum() {
+    let mut rmc = sentence("GPRMC,083559.00,A,4717.11437,N,00833.91522,E,0.004,77.52,150324,,,A");
+    // Corrupt one character of the body, keeping the checksum
+    rmc.replace_range(8..9, "6");
+    assert_eq!(parse_nmea(&rmc), Err(NmeaError::Checksum));
+
+    let zda = "$GPZDA,201530.00,04,07,2024,00,00*00";
+    assert_eq!(parse_nmea(zda), Err(NmeaError::Checksum));
+}
+
+#[test]
+fn missing_or_malformed_checksum() {
+    assert_eq!(
+        parse_nmea("$GPZDA,201530.00,04,07,2024,00,00"),
+        Err(NmeaError::Malformed)
+    );
+    assert_eq!(
+        parse_nmea("$GPZDA,201530.00,04,07,2024,00,00*G1"),
+        Err(NmeaError::Malformed)
+    );
+}
+
+#[test]
+fn no_fix() {
+    let void = sentence("GPRMC,083559.00,V,,,,,,,150324,,,N");
+    assert_eq!(parse_nmea(&void), Err(NmeaError::NoFix));
+    let empty = sentence("GPZDA,,,,,,");
+    assert_eq!(parse_nmea(&empty), Err(NmeaError::NoFix));
+}
+
+#[test]
+fn unsupported_and_invalid_dates() {
+    let gga = sentence("GPGGA,083559.00,4717.11437,N,00833.91522,E,1,08,1.01,499.6,M,48.0,M,,");
+    assert_eq!(parse_nmea(&gga), Err(NmeaError::Unsupported));
+
+    let feb29 = sentence("GPZDA,120000.00,29,02,2023,00,00");
+    assert_eq!(parse_nmea(&feb29), Err(NmeaError::OutOfRange));
+    let leap_second = sentence("GPZDA,235960.00,31,12,2016,00,00");
+    assert_eq!(parse_nmea(&leap_second), Err(NmeaError::OutOfRange));
+}
+
+#[test]
+fn sync_from_nmea_sets_clock_once() {
+    let mock = MockRtc::default();
+    mock.set_time_registers([0x80, 0, 0, 1, 6, 1, 0]); // VL set after power loss
+    let mut rtc = Pcf8563::new(mock.clone());
+
+    let zda = sentence("GPZDA,201530.00,04,07,2024,00,00");
+    assert_eq!(
+        rtc.sync_from_nmea(&zda, 2).unwrap(),
+        SyncOutcome::Updated { offset_secs: None }
+    );
+    assert_eq!(rtc.get_datetime().unwrap(), datetime(24, 7, 4, 20, 15, 30));
+    assert_eq!(
+        rtc.sync_from_nmea(&zda, 2).unwrap(),
+        SyncOutcome::InSync { offset_secs: 0 }
+    );
+
+    let gga = sentence("GPGGA,083559.00,4717.11437,N,00833.91522,E,1,08,1.01,499.6,M,48.0,M,,");
+    assert!(matches!(
+        rtc.sync_from_nmea(&gga, 2),
+        Err(SyncError::Source(NmeaError::Unsupported))
+    ));
+}
@@ -0,0 +1,254 @@
+mod common;
+
+use common::{FakeClock, MockRtc};
+use pcf8563_dd::{
+    DateTime, Pcf8563, RadioClockDecoder, RadioError, RadioProtocol, RadioTime,
+    STOP_RELEASE_DELAY_US,
+};
+
+/// Decoder ticks per millisecond (1 MHz, as `FakeClock`)
+const MS: u64 = 1_000;
+
+fn utc(timestamp: i64) -> DateTime {
+    DateTime::from_unix_timestamp(timestamp).unwrap()
+}
+
+fn bcd(value: u8) -> u8 {
+    ((value / 10) << 4) | (value % 10)
+}
+
+/// Feed `edges` (in ms) and return the result of the last one; all earlier
+/// edges must be accepted silently
+fn feed(
+    decoder: &mut RadioClockDecoder,
+    edges: &[(u64, bool)],
+) -> Result<Option<RadioTime>, RadioError> {
+    let (last, edges) = edges.split_last().unwrap();
+    for &(ms, active) in edges {
+        assert_eq!(decoder.edge(ms * MS, active), Ok(None), "edge at {ms} ms");
+    }
+    decoder.edge(last.0 * MS, last.1)
+}
+
+/// DCF77 frame for the given local (CET/CEST) time, seconds 0-58
+fn dcf77_frame(
+    (year, month, day, weekday, hours, minutes): (u8, u8, u8, u8, u8, u8),
+    summer_time: bool,
+) -> Vec<bool> {
+    let mut frame = vec![false; 59];
+    let mut set = |from: usize, len: usize, value: u8| {
+        for n in 0..len {
+            frame[from + n] = (value >> n) & 1 == 1;
+        }
+    };
+    set(17, 1, summer_time as u8);
+    set(18, 1, !summer_time as u8);
+    set(20, 1, 1);
+    set(21, 7, bcd(minutes));
+    set(29, 6, bcd(hours));
+    set(36, 6, bcd(day));
+    set(42, 3, weekday);
+    set(45, 5, bcd(month));
+    set(50, 8, bcd(year));
+    for (range, parity) in [(21..28, 28), (29..35, 35), (36..58, 58)] {
+        frame[parity] = frame[range].iter().filter(|&&bit| bit).count() % 2 == 1;
+    }
+    frame
+}
+
+/// Receiver edges for `frame` preceded by a minute mark at 2 s; returns the
+/// edges and the time (in ms) of the minute mark ending the frame
+fn dcf77_edges(frame: &[bool]) -> (Vec<(u64, bool)>, u64) {
+    let mut edges = vec![(0, true), (100, false)];
+    for (second, &bit) in frame.iter().enumerate() {
+        let start = 2_000 + second as u64 * 1_000;
+        edges.push((start, true));
+        edges.push((start + if bit { 200 } else { 100 }, false));
+    }
+    // No pulse in the last second of the frame
+    let mark = 2_000 + (frame.len() as u64 + 1) * 1_000;
+    edges.push((mark, true));
+    (edges, mark)
+}
+
+/// MSF frame for the given local (GMT/BST) time as (A, B) bits; index 0 is
+/// the minute marker
+fn msf_frame(
+    (year, month, day, weekday, hours, minutes): (u8, u8, u8, u8, u8, u8),
+    summer_time: bool,
+) -> Vec<(bool, bool)> {
+    let mut frame = vec![(false, false); 60];
+    let mut set = |from: usize, to: usize, value: u8| {
+        for (n, bits) in frame[from..=to].iter_mut().enumerate() {
+            bits.0 = (value >> (to - from - n)) & 1 == 1;
+        }
+    };
+    set(17, 24, bcd(year));
+    set(25, 29, bcd(month));
+    set(30, 35, bcd(day));
+    set(36, 38, weekday);
+    set(39, 44, bcd(hours));
+    set(45, 51, bcd(minutes));
+    set(52, 59, 0b0111_1110);
+    for (range, parity) in [(17..=24, 54), (25..=35, 55), (36..=38, 56), (39..=51, 57)] {
+        let ones = frame[range].iter().filter(|(a, _)| *a).count();
+        frame[parity].1 = ones % 2 == 0;
+    }
+    frame[58].1 = summer_time;
+    frame
+}
+
+/// Receiver edges for `frame` starting with a minute marker at 0; returns
+/// the edges and the time (in ms) of the minute marker ending the frame
+fn msf_edges(frame: &[(bool, bool)]) -> (Vec<(u64, bool)>, u64) {
+    let mut edges = vec![(0, true), (500, false)];
+    for (second, &bits) in frame.iter().enumerate().skip(1) {
+        let start = second as u64 * 1_000;
+        edges.push((start, true));
+        match bits {
+            (false, false) => edges.push((start + 100, false)),
+            (true, false) => edges.push((start + 200, false)),
+            (true, true) => edges.push((start + 300, false)),
+            (false, true) => edges.extend([
+                (start + 100, false),
+                (start + 200, true),
+                (start + 300, false),
+            ]),
+        }
+    }
+    let mark = frame.len() as u64 * 1_000;
+    edges.extend([(mark, true), (mark + 500, false)]);
+    (edges, mark)
+}
+
+#[test]
+fn dcf77_valid_frame() {
+    // 2024-07-04 (Thursday) 22:16 CEST
+    let mut frame = dcf77_frame((24, 7, 4, 4, 22, 16), true);
+    frame[16] = true; // summer time change announced
+    let (edges, mark) = dcf77_edges(&frame);
+
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Dcf77, 1_000_000);
+    let time = feed(&mut decoder, &edges).unwrap().unwrap();
+    assert_eq!(time.utc, utc(1_720_124_160)); // 20:16 UTC
+    assert_eq!(time.local, utc(1_720_124_160 + 7_200));
+    assert!(time.summer_time);
+    assert!(time.summer_time_change);
+    assert!(!time.leap_second);
+    assert_eq!(time.minute_start_ticks, mark * MS);
+}
+
+#[test]
+fn dcf77_parity_error() {
+    let mut frame = dcf77_frame((24, 7, 4, 4, 22, 16), true);
+    frame[21] = !frame[21]; // minute bit flipped, parity not updated
+    let (edges, _) = dcf77_edges(&frame);
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Dcf77, 1_000_000);
+    assert_eq!(feed(&mut decoder, &edges), Err(RadioError::Parity));
+
+    let mut frame = dcf77_frame((24, 7, 4, 4, 22, 16), true);
+    frame[58] = !frame[58];
+    let (edges, _) = dcf77_edges(&frame);
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Dcf77, 1_000_000);
+    assert_eq!(feed(&mut decoder, &edges), Err(RadioError::Parity));
+}
+
+#[test]
+fn dcf77_leap_second() {
+    // The minute before 2017-01-01 01:00 CET had 61 seconds; second 59
+    // carries a 0 pulse and second 60 is the one without a pulse
+    let mut frame = dcf77_frame((17, 1, 1, 7, 1, 0), false);
+    frame[19] = true;
+    frame.push(false);
+    let (edges, mark) = dcf77_edges(&frame);
+
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Dcf77, 1_000_000);
+    let time = feed(&mut decoder, &edges).unwrap().unwrap();
+    assert_eq!(time.utc, utc(1_483_228_800)); // 2017-01-01 00:00 UTC
+    assert!(time.leap_second);
+    assert_eq!(time.minute_start_ticks, mark * MS);
+
+    // 60 seconds without the announcement
+    frame[19] = false;
+    let (edges, _) = dcf77_edges(&frame);
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Dcf77, 1_000_000);
+    assert_eq!(feed(&mut decoder, &edges), Err(RadioError::InvalidFrame));
+}
+
+#[test]
+fn msf_valid_frame() {
+    // 2024-07-04 (Thursday) 21:16 BST
+    let frame = msf_frame((24, 7, 4, 4, 21, 16), true);
+    let (edges, mark) = msf_edges(&frame);
+
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Msf, 1_000_000);
+    let time = feed(&mut decoder, &edges).unwrap().unwrap();
+    assert_eq!(time.utc, utc(1_720_124_160)); // 20:16 UTC
+    assert_eq!(time.local, utc(1_720_124_160 + 3_600));
+    assert!(time.summer_time);
+    assert!(!time.summer_time_change);
+    assert_eq!(time.minute_start_ticks, mark * MS);
+}
+
+#[test]
+fn msf_parity_error() {
+    let mut frame = msf_frame((24, 7, 4, 4, 21, 16), true);
+    frame[54].1 = !frame[54].1;
+    let (edges, _) = msf_edges(&frame);
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Msf, 1_000_000);
+    assert_eq!(feed(&mut decoder, &edges), Err(RadioError::Parity));
+
+    let mut frame = msf_frame((24, 7, 4, 4, 21, 16), true);
+    frame[45].0 = !frame[45].0; // minute bit flipped, parity not updated
+    let (edges, _) = msf_edges(&frame);
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Msf, 1_000_000);
+    assert_eq!(feed(&mut decoder, &edges), Err(RadioError::Parity));
+}
+
+#[test]
+fn msf_leap_second() {
+    // The minute before 2017-01-01 00:00 GMT had 61 seconds; the extra one
+    // is inserted before the fields counted back from the marker
+    let mut frame = msf_frame((17, 1, 1, 0, 0, 0), false);
+    frame.insert(17, (false, false));
+    let (edges, mark) = msf_edges(&frame);
+
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Msf, 1_000_000);
+    let time = feed(&mut decoder, &edges).unwrap().unwrap();
+    assert_eq!(time.utc, utc(1_483_228_800));
+    assert_eq!(time.minute_start_ticks, mark * MS);
+
+    // A negative leap second drops one of the DUT1 seconds
+    let mut frame = msf_frame((17, 1, 1, 0, 0, 0), false);
+    frame.remove(16);
+    let (edges, _) = msf_edges(&frame);
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Msf, 1_000_000);
+    assert_eq!(
+        feed(&mut decoder, &edges).unwrap().unwrap().utc,
+        utc(1_483_228_800)
+    );
+}
+
+#[test]
+fn sync_from_radio_on_minute_mark() {
+    let frame = dcf77_frame((24, 7, 4, 4, 22, 16), true);
+    let (edges, mark) = dcf77_edges(&frame);
+    let mut decoder = RadioClockDecoder::new(RadioProtocol::Dcf77, 1_000_000);
+    let time = feed(&mut decoder, &edges).unwrap().unwrap();
+
+    let mock = MockRtc::default();
+    let mut rtc = Pcf8563::new(mock.clone());
+    let mut mono = FakeClock::new(1_000);
+    let mut delay = mono.clone();
+    // The frame is handled 10 ms after the minute mark
+    mono.now_after(mark * MS + 10 * MS);
+
+    rtc.sync_from_radio(&time, &mut mono, &mut delay).unwrap();
+    assert_eq!(rtc.get_datetime().unwrap(), time.utc);
+    assert_eq!(
+        delay.ticks(),
+        mark * MS + STOP_RELEASE_DELAY_US as u64,
+        "STOP not released in phase with the minute mark"
+    );
+    assert_eq!(mock.regs()[0] & 0x20, 0, "STOP still set");
+}
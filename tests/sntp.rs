@@ -0,0 +1,232 @@
+mod common;
+
+use common::{FakeClock, MockRtc};
+use pcf8563_dd::{
+    DateTime, LeapIndicator, NTP_PACKET_LEN, NtpTimestamp, Pcf8563, STOP_RELEASE_DELAY_US,
+    SntpError, SyncError, SyncOutcome, build_sntp_request, process_sntp_response,
+};
+
+/// Unix time of the NTP era 0 to era 1 rollover, 2036-02-07 06:28:16
+const ERA_1: i64 = 2_085_978_496;
+/// 2024-07-04 20:15:30
+const SERVER: i64 = 1_720_124_130;
+
+/// Server reply with the given header fields
+fn reply(
+    mode: u8,
+    stratum: u8,
+    reference_id: [u8; 4],
+    originate: NtpTimestamp,
+    receive: NtpTimestamp,
+    transmit: NtpTimestamp,
+) -> [u8; NTP_PACKET_LEN] {
+    let mut packet = [0u8; NTP_PACKET_LEN];
+    packet[0] = (4 << 3) | mode;
+    packet[1] = stratum;
+    packet[12..16].copy_from_slice(&reference_id);
+    for (offset, timestamp) in [(24, originate), (32, receive), (40, transmit)] {
+        packet[offset..offset + 4].copy_from_slice(&timestamp.seconds.to_be_bytes());
+        packet[offset + 4..offset + 8].copy_from_slice(&timestamp.fraction.to_be_bytes());
+    }
+    packet
+}
+
+/// Stratum 1 server reply to a request sent at `sent`
+fn server_reply(
+    sent: NtpTimestamp,
+    receive: NtpTimestamp,
+    transmit: NtpTimestamp,
+) -> [u8; NTP_PACKET_LEN] {
+    reply(4, 1, *b"GPS\0", sent, receive, transmit)
+}
+
+fn micros(unix_micros: i64) -> NtpTimestamp {
+    NtpTimestamp::from_unix_micros(unix_micros)
+}
+
+#[test]
+fn timestamp_era_rollover() {
+    let rollover = micros(ERA_1 * 1_000_000);
+    assert_eq!(rollover.seconds, 0);
+    assert_eq!(rollover.to_unix_micros(), ERA_1 * 1_000_000);
+
+    let before = micros(ERA_1 * 1_000_000 - 500_000);
+    assert_eq!(before.seconds, u32::MAX);
+    assert_eq!(before.fraction, 1 << 31);
+    assert_eq!(before.to_unix_micros(), ERA_1 * 1_000_000 - 500_000);
+
+    for unix_micros in [
+        946_684_800_000_000,
+        SERVER * 1_000_000 + 123_456,
+        4_102_444_799_999_999,
+    ] {
+        assert_eq!(micros(unix_micros).to_unix_micros(), unix_micros);
+    }
+}
+
+#[test]
+fn offset_and_delay() {
+    let t1 = SERVER * 1_000_000;
+    // Server 2.5 s ahead, 20 ms each way, 1 ms processing
+    let t2 = t1 + 2_500_000 + 20_000;
+    let t3 = t2 + 1_000;
+    let t4 = t1 + 41_000;
+    let response = server_reply(micros(t1), micros(t2), micros(t3));
+    let measurement = process_sntp_response(&response, micros(t1), micros(t4)).unwrap();
+    assert_eq!(measurement.offset_micros, 2_500_000);
+    assert_eq!(measurement.delay_micros, 40_000);
+    assert_eq!(measurement.stratum, 1);
+    assert_eq!(measurement.leap, LeapIndicator::NoWarning);
+}
+
+#[test]
+fn offset_across_era_rollover() {
+    // Request sent just before the rollover, answered just after it
+    let t1 = ERA_1 * 1_000_000 - 300_000;
+    let t2 = t1 + 1_000_000 + 10_000;
+    let t4 = t1 + 20_000;
+    let response = server_reply(micros(t1), micros(t2), micros(t2));
+    let measurement = process_sntp_response(&response, micros(t1), micros(t4)).unwrap();
+    assert_eq!(measurement.offset_micros, 1_000_000);
+    assert_eq!(measurement.delay_micros, 20_000);
+}
+
+#[test]
+fn kiss_of_death() {
+    let sent = micros(SERVER * 1_000_000);
+    let response = reply(4, 0, *b"RATE", sent, sent, sent);
+    assert_eq!(
+        process_sntp_response(&response, sent, sent),
+        Err(SntpError::KissOfDeath(*b"RATE"))
+    );
+}
+
+#[test]
+fn originate_mismatch() {
+    let sent = micros(SERVER * 1_000_000);
+    let stale = micros(SERVER * 1_000_000 - 1);
+    let response = server_reply(stale, sent, sent);
+    assert_eq!(
+        process_sntp_response(&response, sent, sent),
+        Err(SntpError::UnexpectedOriginate)
+    );
+}
+
+#[test]
+fn rejected_packets() {
+    let sent = micros(SERVER * 1_000_000);
+
+    let broadcast = reply(5, 1, *b"GPS\0", NtpTimestamp::default(), sent, sent);
+    assert_eq!(
+        process_sntp_response(&broadcast, sent, sent),
+        Err(SntpError::NotServerReply)
+    );
+    // Our own request echoed back
+    assert_eq!(
+        process_sntp_response(&build_sntp_request(sent), sent, sent),
+        Err(SntpError::NotServerReply)
+    );
+
+    let mut unsynchronized = server_reply(sent, sent, sent);
+    unsynchronized[0] |= 3 << 6;
+    assert_eq!(
+        process_sntp_response(&unsynchronized, sent, sent),
+        Err(SntpError::Unsynchronized)
+    );
+
+    let response = reply(4, 16, *b"GPS\0", sent, sent, sent);
+    assert_eq!(
+        process_sntp_response(&response, sent, sent),
+        Err(SntpError::InvalidStratum(16))
+    );
+    assert_eq!(
+        process_sntp_response(&server_reply(sent, sent, sent)[..47], sent, sent),
+        Err(SntpError::Truncated)
+    );
+}
+
+/// Sync a powered-up RTC from a server whose clock is `fraction_us` into
+/// `SERVER` at T1; returns the Unix time the RTC was loaded with
+fn sync_from_sntp_at(fraction_us: i64) -> i64 {
+    let mock = MockRtc::default();
+    mock.set_time_registers([0x80, 0, 0, 1, 6, 1, 0]); // VL set after power loss
+    let mut rtc = Pcf8563::new(mock.clone());
+    let mut mono = FakeClock::new(1_000);
+    let mut delay = mono.clone();
+
+    // The RTC does not tick, so the request is stamped from 2000-01-01
+    let request = rtc.sntp_request(&mut mono).unwrap();
+    let received = mono.now_after(30_000);
+    let t1 = request.sent().to_unix_micros();
+    let t4 = request.local_micros_at(received);
+    let offset = SERVER * 1_000_000 + fraction_us - t1;
+    let server = micros((t1 + t4) / 2 + offset);
+    let response = server_reply(request.sent(), server, server);
+
+    let (outcome, measurement) = rtc
+        .sync_from_sntp(&request, &response, received, &mut mono, &mut delay, 1)
+        .unwrap();
+    assert_eq!(outcome, SyncOutcome::Updated { offset_secs: None });
+    assert_eq!(measurement.offset_micros, offset);
+
+    // STOP was released exactly STOP_RELEASE_DELAY_US after the boundary
+    // of the second the RTC was loaded with
+    let released = request.local_micros_at(mono.ticks()) + offset;
+    assert_eq!(released.rem_euclid(1_000_000), STOP_RELEASE_DELAY_US as i64);
+    let loaded = DateTime::from_unix_timestamp(released.div_euclid(1_000_000)).unwrap();
+    assert_eq!(rtc.get_datetime().unwrap(), loaded);
+    assert_eq!(mock.regs()[0] & 0x20, 0, "STOP still set");
+    loaded.to_unix_timestamp()
+}
+
+#[test]
+fn sync_from_sntp_on_second_boundary() {
+    // Early in the second, STOP can still be released within it
+    assert_eq!(sync_from_sntp_at(250_000), SERVER);
+    // Too late for this second, so the RTC is loaded with the next one
+    assert_eq!(sync_from_sntp_at(800_000), SERVER + 1);
+}
+
+#[test]
+fn request_stamp_matches_local_clock() {
+    let mock = MockRtc::default();
+    mock.set_time_registers([0x80, 0, 0, 1, 6, 1, 0]);
+    let mut rtc = Pcf8563::new(mock);
+    let mut mono = FakeClock::new(1_000);
+
+    // With no valid RTC time the base is 2000-01-01 at the last tick reading,
+    // and T1 must be stamped at exactly that tick
+    let request = rtc.sntp_request(&mut mono).unwrap();
+    let base = DateTime::default().to_unix_timestamp() * 1_000_000;
+    assert_eq!(request.sent().to_unix_micros(), base);
+    assert_eq!(request.local_micros_at(mono.ticks()), base);
+    assert_eq!(
+        &request.packet()[40..44],
+        &request.sent().seconds.to_be_bytes()
+    );
+}
+
+#[test]
+fn sync_from_sntp_rejects_kiss_of_death() {
+    let mock = MockRtc::default();
+    mock.set_time_registers([0x80, 0, 0, 1, 6, 1, 0]);
+    let mut rtc = Pcf8563::new(mock.clone());
+    let mut mono = FakeClock::new(1_000);
+    let mut delay = mono.clone();
+
+    let request = rtc.sntp_request(&mut mono).unwrap();
+    let response = reply(
+        4,
+        0,
+        *b"DENY",
+        request.sent(),
+        request.sent(),
+        request.sent(),
+    );
+    let before = mock.regs();
+    assert!(matches!(
+        rtc.sync_from_sntp(&request, &response, mono.ticks(), &mut mono, &mut delay, 1),
+        Err(SyncError::Source(SntpError::KissOfDeath(code))) if code == *b"DENY"
+    ));
+    assert_eq!(mock.regs(), before);
+}
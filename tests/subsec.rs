@@ -0,0 +1,102 @@
+mod common;
+
+use common::{FakeClock, MockRtc};
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, Operation};
+use pcf8563_dd::{DateTime, Field, Monotonic, Pcf8563, RtcError};
+
+/// 2024-07-04 20:15:25
+const TIME: [u8; 7] = [0x25, 0x15, 0x20, 0x04, 0x04, 0x07, 0x24];
+
+/// RTC whose seconds register steps through `seconds`, one value per
+/// transaction, once `after` transactions have passed; the last value stays
+struct Incrementing {
+    rtc: MockRtc,
+    after: usize,
+    seconds: &'static [u8],
+}
+
+impl ErrorType for Incrementing {
+    type Error = ErrorKind;
+}
+
+impl I2c for Incrementing {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if self.after > 0 {
+            self.after -= 1;
+        } else if let Some((&seconds, rest)) = self.seconds.split_first() {
+            let mut time = TIME;
+            time[0] = seconds;
+            self.rtc.set_time_registers(time);
+            if !rest.is_empty() {
+                self.seconds = rest;
+            }
+        }
+        self.rtc.transaction(address, operations)
+    }
+}
+
+fn rtc(seconds: &'static [u8]) -> Pcf8563<pcf8563_dd::Pcf8563Interface<Incrementing>, ErrorKind> {
+    let mock = MockRtc::default();
+    mock.set_time_registers(TIME);
+    Pcf8563::new(Incrementing {
+        rtc: mock,
+        after: 3,
+        seconds,
+    })
+}
+
+#[test]
+fn subsec_after_increment() {
+    let mut rtc = rtc(&[0x26]);
+    let mut mono = FakeClock::new(1_000);
+    let subsec = rtc.get_datetime_with_subsec(&mut mono).unwrap();
+    let mut expected = DateTime::from_unix_timestamp(1_720_124_126).unwrap();
+    expected.weekday = 4;
+    assert_eq!(subsec.datetime, expected);
+    assert!(subsec.millis < 10, "{subsec:?}");
+}
+
+#[test]
+fn corrupt_seconds_at_increment() {
+    // Only the read that sees the increment is corrupt
+    let mut rtc = rtc(&[0x7F, 0x26]);
+    let mut mono = FakeClock::new(1_000);
+    assert!(matches!(
+        rtc.get_datetime_with_subsec(&mut mono),
+        Err(RtcError::InvalidRegisterValue {
+            field: Field::Seconds,
+            raw: 0x7F
+        })
+    ));
+}
+
+/// Monotonic clock misreporting its rate as 0 Hz
+struct Stalled;
+
+impl Monotonic for Stalled {
+    fn now(&mut self) -> u64 {
+        0
+    }
+
+    fn tick_hz(&self) -> u32 {
+        0
+    }
+}
+
+#[test]
+fn zero_tick_rate() {
+    let mut rtc = rtc(&[0x26]);
+    assert!(matches!(
+        rtc.get_datetime_with_subsec(&mut Stalled),
+        Err(RtcError::InvalidTickRate)
+    ));
+    let mut delay = FakeClock::new(0);
+    assert!(matches!(
+        rtc.set_datetime_at(&DateTime::default(), 0, &mut Stalled, &mut delay),
+        Err(RtcError::InvalidTickRate)
+    ));
+}
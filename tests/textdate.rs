@@ -0,0 +1,202 @@
+use pcf8563_dd::{DateParseError, DateTime, parse_cclk, parse_http_date, parse_rfc2822};
+
+const HOUR: i64 = 3_600;
+const MINUTE: i64 = 60;
+/// 2024-11-06 08:49:37
+const NOV_6: i64 = 1_730_882_977;
+/// 2025-11-21 09:55:06
+const NOV_21: i64 = 1_763_718_906;
+/// 2024-07-04 20:15:30
+const JULY_4: i64 = 1_720_124_130;
+
+fn utc(timestamp: i64) -> Result<DateTime, DateParseError> {
+    Ok(DateTime::from_unix_timestamp(timestamp).unwrap())
+}
+
+#[test]
+fn http_date_formats() {
+    assert_eq!(parse_http_date("Wed, 06 Nov 2024 08:49:37 GMT"), utc(NOV_6));
+    assert_eq!(
+        parse_http_date("Date: Wed, 06 Nov 2024 08:49:37 GMT"),
+        utc(NOV_6)
+    );
+    assert_eq!(
+        parse_http_date("Wednesday, 06-Nov-24 08:49:37 GMT"),
+        utc(NOV_6)
+    );
+    assert_eq!(parse_http_date("Wed Nov  6 08:49:37 2024"), utc(NOV_6));
+    // Leap second clamped to 59
+    assert_eq!(
+        parse_http_date("Tue, 31 Dec 2024 23:59:60 GMT"),
+        utc(1_735_689_599)
+    );
+}
+
+#[test]
+fn http_date_errors() {
+    assert_eq!(
+        parse_http_date("Wed, 06 Nov 2024 08:49:37 PST"),
+        Err(DateParseError::Malformed)
+    );
+    assert_eq!(
+        parse_http_date("Wed, 6 Nov 2024 08:49:37 GMT"),
+        Err(DateParseError::Malformed)
+    );
+    assert_eq!(
+        parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+        Err(DateParseError::OutOfRange)
+    );
+    assert_eq!(
+        parse_http_date("Fri, 30 Feb 2024 08:49:37 GMT"),
+        Err(DateParseError::OutOfRange)
+    );
+}
+
+#[test]
+fn rfc2822_offsets() {
+    assert_eq!(
+        parse_rfc2822("Fri, 21 Nov 2025 09:55:06 -0600"),
+        utc(NOV_21 + 6 * HOUR)
+    );
+    assert_eq!(
+        parse_rfc2822("Fri, 21 Nov 2025 09:55:06 +0000"),
+        utc(NOV_21)
+    );
+    assert_eq!(
+        parse_rfc2822("21 Nov 2025 09:55:06 +0530"),
+        utc(NOV_21 - 5 * HOUR - 30 * MINUTE)
+    );
+    assert_eq!(
+        parse_rfc2822("21 Nov 2025 09:55:06 -0330"),
+        utc(NOV_21 + 3 * HOUR + 30 * MINUTE)
+    );
+    assert_eq!(
+        parse_rfc2822("21 Nov 2025 09:55:06 +1345"),
+        utc(NOV_21 - 13 * HOUR - 45 * MINUTE)
+    );
+    assert_eq!(
+        parse_rfc2822("21 Nov 2025 09:55:06 +1400"),
+        utc(NOV_21 - 14 * HOUR)
+    );
+    assert_eq!(
+        parse_rfc2822("21 Nov 2025 09:55:06 -1400"),
+        utc(NOV_21 + 14 * HOUR)
+    );
+}
+
+#[test]
+fn rfc2822_obsolete_syntax() {
+    assert_eq!(
+        parse_rfc2822("21 Nov 2025 09:55:06 EST"),
+        utc(NOV_21 + 5 * HOUR)
+    );
+    assert_eq!(
+        parse_rfc2822("21 Nov 2025 09:55:06 PDT"),
+        utc(NOV_21 + 7 * HOUR)
+    );
+    assert_eq!(parse_rfc2822("21 Nov 2025 09:55:06 GMT"), utc(NOV_21));
+    assert_eq!(parse_rfc2822("21 Nov 2025 09:55:06 Z"), utc(NOV_21));
+    assert_eq!(parse_rfc2822("21 Nov 25 09:55 +0000"), utc(NOV_21 - 6));
+}
+
+#[test]
+fn rfc2822_errors() {
+    for value in [
+        "21 Nov 2025 09:55:06 +06",
+        "21 Nov 2025 09:55:06 0600",
+        "21 Nov 2025 09:55:06 +0660",
+        "21 Nov 2025 09:55:06 +1401",
+        "21 Nov 2025 09:55:06 -1500",
+        "21 Nov 2025 09:55:06 +9959",
+        "21 Nov 2025 09:55:06",
+        "Fry, 21 Nov 2025 09:55:06 +0000",
+        "21 Nov 2025 09:55:06 +0000 (UTC)",
+    ] {
+        assert_eq!(
+            parse_rfc2822(value),
+            Err(DateParseError::Malformed),
+            "{value}"
+        );
+    }
+    // The UTC result must be in 2000-2099
+    assert_eq!(
+        parse_rfc2822("Sat, 01 Jan 2000 00:30:00 +0100"),
+        Err(DateParseError::OutOfRange)
+    );
+    assert_eq!(
+        parse_rfc2822("Thu, 31 Dec 2099 23:30:00 -0100"),
+        Err(DateParseError::OutOfRange)
+    );
+    assert_eq!(
+        parse_rfc2822("21 Nov 97 09:55:06 -0600"),
+        Err(DateParseError::OutOfRange)
+    );
+}
+
+#[test]
+fn cclk_quarter_hour_offsets() {
+    assert_eq!(parse_cclk("+CCLK: \"24/07/04,20:15:30+00\""), utc(JULY_4));
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04,20:15:30+08\""),
+        utc(JULY_4 - 2 * HOUR)
+    );
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04,20:15:30-20\""),
+        utc(JULY_4 + 5 * HOUR)
+    );
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04,20:15:30+22\""),
+        utc(JULY_4 - 5 * HOUR - 30 * MINUTE)
+    );
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04,20:15:30-14\""),
+        utc(JULY_4 + 3 * HOUR + 30 * MINUTE)
+    );
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04,20:15:30+3\""),
+        utc(JULY_4 - 45 * MINUTE)
+    );
+    // ±56 quarter hours (±14 h) is the limit
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04,20:15:30+56\""),
+        utc(JULY_4 - 14 * HOUR)
+    );
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04,20:15:30-56\""),
+        utc(JULY_4 + 14 * HOUR)
+    );
+    for zone in ["+57", "-57", "+99"] {
+        assert_eq!(
+            parse_cclk(&format!("+CCLK: \"24/07/04,20:15:30{zone}\"")),
+            Err(DateParseError::Malformed),
+            "{zone}"
+        );
+    }
+    // Negative offset carrying into the next year
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/12/31,22:00:00-14\""),
+        utc(1_735_695_000)
+    );
+}
+
+#[test]
+fn cclk_variations() {
+    assert_eq!(parse_cclk("\"24/07/04,20:15:30\""), utc(JULY_4));
+    assert_eq!(parse_cclk("24/07/04,20:15:30"), utc(JULY_4));
+    assert_eq!(
+        parse_cclk("+CCLK: \"2024/07/04,22:15:30+08\"\r\n"),
+        utc(JULY_4)
+    );
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04 20:15:30+00\""),
+        Err(DateParseError::Malformed)
+    );
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/07/04,20:15:30+\""),
+        Err(DateParseError::Malformed)
+    );
+    assert_eq!(
+        parse_cclk("+CCLK: \"24/13/04,20:15:30+00\""),
+        Err(DateParseError::OutOfRange)
+    );
+}
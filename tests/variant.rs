@@ -0,0 +1,31 @@
+mod common;
+
+use common::MockRtc;
+use pcf8563_dd::{Pcf8563, Variant};
+
+/// Registers after power-on reset, clock running
+fn reset_registers() -> [u8; 16] {
+    let mut regs = [0u8; 16];
+    regs[0x02] = 0x80; // VL
+    regs[0x03..0x09].copy_from_slice(&[0x00, 0x00, 0x01, 0x06, 0x01, 0x00]);
+    regs[0x09..0x0D].fill(0x80); // alarms disabled
+    regs[0x0D] = 0x80; // CLKOUT enabled, 32.768 kHz
+    regs[0x0E] = 0x03; // timer disabled, 1/60 Hz
+    regs
+}
+
+#[test]
+fn detect_variant_is_read_only_and_inconclusive() {
+    // Undefined Timer_control bits reading back as 0 and as 1
+    for undefined in [0x00, 0x7C] {
+        let mut regs = reset_registers();
+        regs[0x0E] |= undefined;
+        let mock = MockRtc::new(regs);
+        let mut rtc = Pcf8563::new(mock.clone());
+
+        assert!(rtc.probe().unwrap());
+        assert_eq!(rtc.detect_variant().unwrap(), Variant::Unknown);
+        assert_eq!(mock.writes(), 0, "detect_variant wrote to the device");
+        assert_eq!(mock.regs(), regs);
+    }
+}